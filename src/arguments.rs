@@ -1,4 +1,10 @@
+use crate::cycles::DEFAULT_CYCLE_LIMIT;
+use crate::error::EXIT_USAGE;
+use crate::features::FeatureSelection;
+use crate::graph::DependencyKind;
 use argh::FromArgs;
+use std::path::{Path, PathBuf};
+use std::process;
 use std::str::FromStr;
 
 #[derive(FromArgs)]
@@ -9,16 +15,120 @@ pub struct Arguments {
     #[argh(option, default = "\".\".to_string()", short = 'd')]
     pub directory: String,
 
-    /// specifies the type of the output file which is going to be stored in the current working directory.
-    /// Default is set to SVG.
+    /// the output of `cargo metadata --format-version 1` to build the graph from, instead of reading the manifests.
+    /// Use `-` to read it from stdin.
+    #[argh(option)]
+    pub metadata: Option<String>,
+
+    /// specifies the type of the output file, either `svg`, `mmd`, `dot` or `html`. Default is set to SVG.
+    /// The file is written to `--output`, where `-` stands for stdout, or else to `workspace-analyzer.<format>`
+    /// in the current working directory.
     #[argh(option, short = 'o', default = "FileExportOptions::SVG")]
     pub output_format: FileExportOptions,
+
+    /// the path to write the diagram to, `-` writes it to stdout instead of the report.
+    /// Defaults to `workspace-analyzer.<format>` in the current working directory.
+    #[argh(option)]
+    pub output: Option<String>,
+
+    /// render the SVG with the Mermaid CLI (`mmdc`) instead of the built-in renderer.
+    #[argh(switch)]
+    pub mmdc: bool,
+
+    /// the format of the report printed to stdout, either `table` or `json`.
+    /// Default is set to table.
+    #[argh(option, short = 'f', default = "ReportFormat::Table")]
+    pub format: ReportFormat,
+
+    /// the members to analyze, either `all`, the `default-members` which `cargo build` selects without `-p`,
+    /// or `default-members+deps` to include the workspace members they transitively depend on. Only their own
+    /// dev-dependencies are followed, unless `dev` is selected with `--kinds`. Defaults to all.
+    #[argh(option, default = "Scope::All")]
+    pub scope: Scope,
+
+    /// the maximum number of circular dependencies to list. Defaults to 100.
+    #[argh(option, default = "DEFAULT_CYCLE_LIMIT")]
+    pub cycle_limit: usize,
+
+    /// collapse the packages which depend on each other into a single node, which shows the layering of the workspace.
+    #[argh(switch)]
+    pub condense: bool,
+
+    /// print the architectural layers of the workspace, and group the packages by layer in the Mermaid diagram.
+    #[argh(switch)]
+    pub layers: bool,
+
+    /// a glob of directories whose members are left out, relative to the workspace directory,
+    /// e.g. `fixtures` or `**/templates`. Can be given multiple times.
+    #[argh(option)]
+    pub exclude_dir: Vec<String>,
+
+    /// fail if any manifest is skipped, e.g. since it could not be parsed or does not exist.
+    #[argh(switch)]
+    pub strict: bool,
+
+    /// the kinds of dependencies to analyze, given as a comma separated list of `normal`, `build` and `dev`, or `all`.
+    /// Defaults to all.
+    #[argh(option, short = 'k')]
+    pub kinds: Option<DependencyKinds>,
+
+    /// the target triple to evaluate platform specific dependencies for, e.g. `x86_64-pc-windows-msvc`.
+    /// By default, the dependencies of all platforms are included.
+    #[argh(option)]
+    pub target: Option<String>,
+
+    /// an additional cfg option to evaluate platform specific dependencies with, e.g. `unix` or `target_os="linux"`.
+    /// Can be given multiple times.
+    #[argh(option)]
+    pub cfg: Vec<String>,
+
+    /// the features to evaluate optional dependencies for, given as a comma separated list.
+    /// Use `package/feature` to select a feature of a single package. Can be given multiple times.
+    /// By default, optional dependencies are included and drawn dashed.
+    #[argh(option)]
+    pub features: Vec<String>,
+
+    /// evaluate optional dependencies with all features of every package enabled.
+    #[argh(switch)]
+    pub all_features: bool,
+
+    /// evaluate optional dependencies without enabling the default features.
+    #[argh(switch)]
+    pub no_default_features: bool,
+
+    /// a package to evaluate optional dependencies for with all of its features enabled. Can be given multiple times.
+    #[argh(option)]
+    pub all_features_for: Vec<String>,
+
+    /// a package to evaluate optional dependencies for without its default features. Can be given multiple times.
+    #[argh(option)]
+    pub no_default_features_for: Vec<String>,
+
+    /// show which external crates the workspace members pull in, based on the lockfile.
+    #[argh(switch)]
+    pub external: bool,
+
+    /// report external crates which are resolved in more than one version, and which members pull them in.
+    #[argh(switch)]
+    pub duplicates: bool,
+
+    /// report external dependencies which members declare with differing versions, features or default features.
+    #[argh(switch)]
+    pub drift: bool,
+
+    /// the lockfile to read the external crates from.
+    /// Defaults to the `Cargo.lock` within the workspace directory.
+    #[argh(option)]
+    pub lockfile: Option<String>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(clippy::upper_case_acronyms)]
 pub enum FileExportOptions {
     SVG,
     MMD,
+    DOT,
+    HTML,
 }
 
 impl FromStr for FileExportOptions {
@@ -28,12 +138,158 @@ impl FromStr for FileExportOptions {
         match s.to_lowercase().as_str() {
             "svg" => Ok(FileExportOptions::SVG),
             "mmd" => Ok(FileExportOptions::MMD),
-            _ => Err(format!("Invalid output format: {}. Allowed: svg, mmd", s)),
+            "dot" => Ok(FileExportOptions::DOT),
+            "html" => Ok(FileExportOptions::HTML),
+            _ => Err(format!(
+                "Invalid output format: {}. Allowed: svg, mmd, dot, html",
+                s
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    Table,
+    Json,
+}
+
+impl FromStr for ReportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "table" => Ok(ReportFormat::Table),
+            "json" => Ok(ReportFormat::Json),
+            _ => Err(format!(
+                "Invalid report format: {}. Allowed: table, json",
+                s
+            )),
         }
     }
 }
 
+/// The members which are analyzed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scope {
+    All,
+    DefaultMembers,
+    DefaultMembersWithDependencies,
+}
+
+impl FromStr for Scope {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "all" => Ok(Scope::All),
+            "default-members" => Ok(Scope::DefaultMembers),
+            "default-members+deps" => Ok(Scope::DefaultMembersWithDependencies),
+            _ => Err(format!(
+                "Invalid scope: {}. Allowed: all, default-members, default-members+deps",
+                s
+            )),
+        }
+    }
+}
+
+/// The selection of dependency kinds which are taken into account.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DependencyKinds(pub Vec<DependencyKind>);
+
+impl DependencyKinds {
+    pub fn all() -> Self {
+        DependencyKinds(DependencyKind::ALL.to_vec())
+    }
+}
+
+impl FromStr for DependencyKinds {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.trim().eq_ignore_ascii_case("all") {
+            return Ok(DependencyKinds::all());
+        }
+
+        let mut kinds = s
+            .split([',', '+'])
+            .map(DependencyKind::from_str)
+            .collect::<Result<Vec<_>, _>>()?;
+        kinds.sort();
+        kinds.dedup();
+        Ok(DependencyKinds(kinds))
+    }
+}
+
+impl Arguments {
+    /// Returns the selected features, or `None` if no feature flag was given.
+    pub fn feature_selection(&self) -> Option<FeatureSelection> {
+        if self.features.is_empty()
+            && !self.all_features
+            && !self.no_default_features
+            && self.all_features_for.is_empty()
+            && self.no_default_features_for.is_empty()
+        {
+            return None;
+        }
+
+        Some(FeatureSelection {
+            features: self
+                .features
+                .iter()
+                .flat_map(|features| features.split([',', ' ']))
+                .filter(|feature| !feature.is_empty())
+                .map(|feature| feature.to_string())
+                .collect(),
+            all_features: self.all_features,
+            no_default_features: self.no_default_features,
+            all_features_for: self.all_features_for.clone(),
+            no_default_features_for: self.no_default_features_for.clone(),
+        })
+    }
+
+    /// Returns the selected kinds of dependencies, which are all kinds if none were given.
+    pub fn dependency_kinds(&self) -> DependencyKinds {
+        self.kinds.clone().unwrap_or_else(DependencyKinds::all)
+    }
+
+    /// Whether dev-dependencies were explicitly selected, so they are followed by the transitive scope as well.
+    pub fn dev_dependencies_selected(&self) -> bool {
+        self.kinds
+            .as_ref()
+            .is_some_and(|kinds| kinds.0.contains(&DependencyKind::Dev))
+    }
+
+    /// Returns the path of the lockfile to analyze.
+    pub fn lockfile_path(&self) -> PathBuf {
+        match &self.lockfile {
+            Some(path) => PathBuf::from(path),
+            None => Path::new(&self.directory).join("Cargo.lock"),
+        }
+    }
+}
+
+/// Parses the command line arguments.
+/// Exits with the usage exit code if they are invalid, and prints the help if it is requested.
 pub fn get_args() -> Arguments {
-    let args: Arguments = argh::from_env();
-    args
+    let strings: Vec<String> = std::env::args().collect();
+    let command = Path::new(&strings[0])
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or(&strings[0]);
+    let args: Vec<&str> = strings[1..].iter().map(|s| s.as_str()).collect();
+
+    Arguments::from_args(&[command], &args).unwrap_or_else(|early_exit| match early_exit.status {
+        Ok(()) => {
+            println!("{}", early_exit.output);
+            process::exit(0)
+        }
+        Err(()) => {
+            eprintln!(
+                "{}\nRun {} --help for more information.",
+                early_exit.output, command
+            );
+            process::exit(EXIT_USAGE as i32)
+        }
+    })
 }
@@ -0,0 +1,100 @@
+use crate::cycles::CycleAnalysis;
+use crate::graph::Graph;
+use std::collections::HashMap;
+
+impl Graph {
+    /// Collapses each strongly connected component with more than one package into a single node, named like
+    /// `Component 1` after its index, which yields an acyclic graph. Dependencies of packages on themselves are
+    /// removed as well. The details of the collapsed edges are kept.
+    pub fn condense(&self, cycles: &CycleAnalysis) -> Graph {
+        let mut nodes: HashMap<&str, String> = HashMap::new();
+        let mut condensed = Graph::new();
+
+        for (i, component) in cycles.components.iter().enumerate() {
+            if component.len() > 1 {
+                let name = format!("Component {}", i + 1);
+                for package in component {
+                    nodes.insert(package, name.clone());
+                }
+                condensed.groups.insert(name, component.clone());
+            }
+        }
+        let node = |package: &str| {
+            nodes
+                .get(package)
+                .cloned()
+                .unwrap_or_else(|| package.to_string())
+        };
+
+        for (package, dependencies) in &self.adjacency_list {
+            let from = node(package);
+            condensed.adjacency_list.entry(from.clone()).or_default();
+            if from == *package {
+                if let Some(features) = self.features.get(package) {
+                    condensed.features.insert(from.clone(), features.clone());
+                }
+                if self.is_external_path(package) {
+                    condensed.external_paths.insert(from.clone());
+                }
+            }
+
+            for dep in dependencies {
+                let to = node(dep);
+                if from == to {
+                    continue;
+                }
+                for info in self.get_edge_infos(package, dep) {
+                    condensed.add_edge_with_info(&from, &to, info.clone());
+                }
+            }
+        }
+
+        for dependencies in condensed.adjacency_list.values_mut() {
+            dependencies.sort();
+        }
+        condensed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cycles::DEFAULT_CYCLE_LIMIT;
+    use crate::graph::{DependencyKind, EdgeInfo, Graph};
+
+    #[test]
+    fn test_condense() {
+        let mut graph = Graph::new();
+        graph.add_edge("app", "a");
+        graph.add_edge("a", "b");
+        graph.add_edge_with_info(
+            "b",
+            "a",
+            EdgeInfo {
+                kind: DependencyKind::Dev,
+                ..Default::default()
+            },
+        );
+        graph.add_edge("b", "core");
+        graph.add_edge("a", "core");
+        graph.add_edge("core", "core");
+        graph.add_edge("core", "serde");
+        graph.adjacency_list.remove("serde");
+
+        let cycles = graph.analyze_cycles(DEFAULT_CYCLE_LIMIT);
+        let condensed = graph.condense(&cycles);
+
+        let mut nodes: Vec<&String> = condensed.adjacency_list.keys().collect();
+        nodes.sort();
+        assert_eq!(nodes, vec!["Component 1", "app", "core"]);
+        assert_eq!(condensed.adjacency_list["app"], vec!["Component 1"]);
+        // both edges to `core` are merged, and the dependency of `core` on itself is removed
+        assert_eq!(condensed.adjacency_list["Component 1"], vec!["core"]);
+        assert_eq!(condensed.get_edge_infos("Component 1", "core").len(), 2);
+        assert_eq!(condensed.adjacency_list["core"], vec!["serde"]);
+        assert_eq!(
+            condensed.get_group_members("Component 1"),
+            Some(&["a".to_string(), "b".to_string()][..])
+        );
+        assert!(condensed.analyze_cycles(DEFAULT_CYCLE_LIMIT).is_empty());
+    }
+}
@@ -0,0 +1,782 @@
+use crate::graph::Graph;
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap};
+
+/// The number of elementary cycles which are enumerated by default.
+pub const DEFAULT_CYCLE_LIMIT: usize = 100;
+
+/// Components with at most this many edges are searched for a minimum feedback arc set,
+/// larger ones are broken up heuristically.
+const EXACT_FEEDBACK_ARC_SET_EDGES: usize = 16;
+
+/// The circular dependencies of a graph, which are shared by all outputs.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct CycleAnalysis {
+    /// The strongly connected components with more than one package, or with a package depending on itself.
+    /// Every circular dependency lies within one of them.
+    pub components: Vec<Vec<String>>,
+    /// The elementary cycles, each given by its packages in the order of the dependencies.
+    /// The last package depends on the first one, which closes the cycle.
+    pub cycles: Vec<Vec<String>>,
+    /// Whether there are more elementary cycles than the limit, which were left out.
+    pub truncated: bool,
+    /// The edges which are part of a cycle.
+    pub edges: BTreeSet<(String, String)>,
+    /// The dependencies whose removal makes the graph acyclic, ranked by the number of cycles they are part of.
+    pub feedback_arc_set: Vec<FeedbackEdge>,
+}
+
+/// A dependency which needs to be removed to break the cycles of its component.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct FeedbackEdge {
+    pub from: String,
+    pub to: String,
+    /// The index of the component the dependency belongs to.
+    pub component: usize,
+    /// The number of listed elementary cycles the dependency is part of.
+    pub cycles: usize,
+}
+
+impl CycleAnalysis {
+    /// Whether the edge from `from` to `to` is part of a cycle.
+    pub fn contains_edge(&self, from: &str, to: &str) -> bool {
+        self.edges.contains(&(from.to_string(), to.to_string()))
+    }
+
+    /// Returns the index of the component the package belongs to, if it is part of a cycle.
+    pub fn component_of(&self, package: &str) -> Option<usize> {
+        self.components
+            .iter()
+            .position(|component| component.iter().any(|p| p == package))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.components.is_empty()
+    }
+}
+
+/// Returns the packages of a cycle, separated by arrows and closed with the first package, e.g. `a → b → a`.
+pub fn cycle_text(path: &[String]) -> String {
+    let mut packages: Vec<&str> = path.iter().map(|p| p.as_str()).collect();
+    packages.extend(path.first().map(|p| p.as_str()));
+    packages.join(" → ")
+}
+
+impl Graph {
+    /// Finds the circular dependencies between the packages of the graph.
+    /// The strongly connected components are found with Tarjan's algorithm, and at most `limit` elementary cycles are
+    /// enumerated with Johnson's algorithm. Edges to packages which aren't part of the graph are ignored.
+    pub fn analyze_cycles(&self, limit: usize) -> CycleAnalysis {
+        let adjacency = IndexedGraph::new(self);
+        let mut analysis = CycleAnalysis::default();
+
+        let mut components: Vec<Vec<usize>> = adjacency
+            .strongly_connected_components()
+            .into_iter()
+            .filter(|component| {
+                component.len() > 1 || adjacency.edges[component[0]].contains(&component[0])
+            })
+            .collect();
+        for component in &mut components {
+            component.sort();
+        }
+        components.sort();
+
+        for component in &components {
+            for &from in component {
+                for &to in &adjacency.edges[from] {
+                    if component.binary_search(&to).is_ok() {
+                        analysis.edges.insert((
+                            adjacency.names[from].to_string(),
+                            adjacency.names[to].to_string(),
+                        ));
+                    }
+                }
+            }
+            analysis.components.push(
+                component
+                    .iter()
+                    .map(|&i| adjacency.names[i].to_string())
+                    .collect(),
+            );
+        }
+
+        let mut search = CycleSearch {
+            graph: &adjacency,
+            limit,
+            component: Vec::new(),
+            start: 0,
+            blocked: vec![false; adjacency.names.len()],
+            blocked_by: vec![Vec::new(); adjacency.names.len()],
+            stack: Vec::new(),
+            cycles: Vec::new(),
+            truncated: false,
+        };
+        'components: for component in &components {
+            search.component = component.clone();
+            for &start in component {
+                search.start = start;
+                for &node in component {
+                    search.blocked[node] = false;
+                    search.blocked_by[node].clear();
+                }
+                search.circuit(start);
+                if search.truncated {
+                    break 'components;
+                }
+            }
+        }
+
+        analysis.truncated = search.truncated;
+        for (index, component) in components.iter().enumerate() {
+            let cycle_count = |from: usize, to: usize| {
+                search
+                    .cycles
+                    .iter()
+                    .filter(|cycle| {
+                        cycle
+                            .iter()
+                            .enumerate()
+                            .any(|(i, &node)| node == from && cycle[(i + 1) % cycle.len()] == to)
+                    })
+                    .count()
+            };
+            let mut edges: Vec<(usize, usize, usize)> = component
+                .iter()
+                .flat_map(|&from| {
+                    adjacency.edges[from]
+                        .iter()
+                        .filter(|to| component.binary_search(to).is_ok())
+                        .map(move |&to| (from, to))
+                })
+                .map(|(from, to)| (from, to, cycle_count(from, to)))
+                .collect();
+            // the edges which are part of the most cycles are tried first
+            edges.sort_by(|a, b| b.2.cmp(&a.2).then((a.0, a.1).cmp(&(b.0, b.1))));
+
+            for (from, to, cycles) in feedback_arc_set(component, &edges) {
+                analysis.feedback_arc_set.push(FeedbackEdge {
+                    from: adjacency.names[from].to_string(),
+                    to: adjacency.names[to].to_string(),
+                    component: index,
+                    cycles,
+                });
+            }
+        }
+        analysis.feedback_arc_set.sort_by(|a, b| {
+            b.cycles
+                .cmp(&a.cycles)
+                .then((&a.from, &a.to).cmp(&(&b.from, &b.to)))
+        });
+
+        analysis.cycles = search
+            .cycles
+            .into_iter()
+            .map(|cycle| {
+                cycle
+                    .iter()
+                    .map(|&i| adjacency.names[i].to_string())
+                    .collect()
+            })
+            .collect();
+        analysis
+    }
+}
+
+/// Returns a small set of the edges of a strongly connected component, whose removal makes the component acyclic.
+/// The edges are given as `(from, to, cycles)`, ordered by preference. Small components are searched for a minimum
+/// set, larger ones are ordered with the heuristic of Eades, Lin and Smyth, whose backward edges are then reduced to
+/// a minimal set.
+fn feedback_arc_set(
+    component: &[usize],
+    edges: &[(usize, usize, usize)],
+) -> Vec<(usize, usize, usize)> {
+    // a dependency of a package on itself has to be removed in any case
+    let (self_loops, edges): (Vec<_>, Vec<_>) =
+        edges.iter().copied().partition(|(from, to, _)| from == to);
+    let is_acyclic = |removed: &[bool]| {
+        let kept: Vec<(usize, usize)> = edges
+            .iter()
+            .zip(removed)
+            .filter(|(_, removed)| !**removed)
+            .map(|(&(from, to, _), _)| (from, to))
+            .collect();
+        is_acyclic(component, &kept)
+    };
+
+    let mut removed = vec![false; edges.len()];
+    if edges.len() <= EXACT_FEEDBACK_ARC_SET_EDGES {
+        'search: for size in 0..=edges.len() {
+            for combination in combinations(edges.len(), size) {
+                removed.iter_mut().for_each(|r| *r = false);
+                for i in combination {
+                    removed[i] = true;
+                }
+                if is_acyclic(&removed) {
+                    break 'search;
+                }
+            }
+        }
+    } else {
+        let position = eades_order(component, &edges);
+        for (i, (from, to, _)) in edges.iter().enumerate() {
+            removed[i] = position[from] >= position[to];
+        }
+        // put back the edges which don't close a cycle on their own, starting with the least preferred ones
+        for i in (0..edges.len()).rev() {
+            if removed[i] {
+                removed[i] = false;
+                if !is_acyclic(&removed) {
+                    removed[i] = true;
+                }
+            }
+        }
+    }
+
+    self_loops
+        .into_iter()
+        .chain(
+            edges
+                .iter()
+                .zip(&removed)
+                .filter(|(_, removed)| **removed)
+                .map(|(edge, _)| *edge),
+        )
+        .collect()
+}
+
+/// Returns all combinations of `size` indices below `n`, in lexicographic order.
+fn combinations(n: usize, size: usize) -> impl Iterator<Item = Vec<usize>> {
+    let mut next = (size <= n).then(|| (0..size).collect::<Vec<usize>>());
+    std::iter::from_fn(move || {
+        let current = next.take()?;
+        let mut following = current.clone();
+        if let Some(i) = (0..size).rev().find(|&i| following[i] < n - size + i) {
+            following[i] += 1;
+            for j in i + 1..size {
+                following[j] = following[j - 1] + 1;
+            }
+            next = Some(following);
+        }
+        Some(current)
+    })
+}
+
+/// Whether the packages of the component are free of cycles, when only the given edges are kept.
+fn is_acyclic(component: &[usize], edges: &[(usize, usize)]) -> bool {
+    let mut in_degree: HashMap<usize, usize> = component.iter().map(|&node| (node, 0)).collect();
+    for (_, to) in edges {
+        *in_degree.get_mut(to).unwrap() += 1;
+    }
+
+    let mut ready: Vec<usize> = component
+        .iter()
+        .copied()
+        .filter(|node| in_degree[node] == 0)
+        .collect();
+    let mut visited = 0;
+    while let Some(node) = ready.pop() {
+        visited += 1;
+        for (_, to) in edges.iter().filter(|(from, _)| *from == node) {
+            let degree = in_degree.get_mut(to).unwrap();
+            *degree -= 1;
+            if *degree == 0 {
+                ready.push(*to);
+            }
+        }
+    }
+    visited == component.len()
+}
+
+/// Orders the packages of the component with the heuristic of Eades, Lin and Smyth, such that few edges point
+/// backwards. Returns the position of each package.
+fn eades_order(component: &[usize], edges: &[(usize, usize, usize)]) -> HashMap<usize, usize> {
+    let mut remaining: BTreeSet<usize> = component.iter().copied().collect();
+    let degree = |remaining: &BTreeSet<usize>, node: usize, outgoing: bool| {
+        edges
+            .iter()
+            .filter(|(from, to, _)| {
+                let (this, other) = if outgoing { (from, to) } else { (to, from) };
+                *this == node && remaining.contains(other)
+            })
+            .count()
+    };
+
+    let mut head = Vec::new();
+    let mut tail = Vec::new();
+    while !remaining.is_empty() {
+        if let Some(sink) = remaining
+            .iter()
+            .copied()
+            .find(|&node| degree(&remaining, node, true) == 0)
+        {
+            remaining.remove(&sink);
+            tail.push(sink);
+        } else if let Some(source) = remaining
+            .iter()
+            .copied()
+            .find(|&node| degree(&remaining, node, false) == 0)
+        {
+            remaining.remove(&source);
+            head.push(source);
+        } else {
+            let node = remaining
+                .iter()
+                .copied()
+                .max_by_key(|&node| {
+                    (
+                        degree(&remaining, node, true) as isize
+                            - degree(&remaining, node, false) as isize,
+                        std::cmp::Reverse(node),
+                    )
+                })
+                .unwrap();
+            remaining.remove(&node);
+            head.push(node);
+        }
+    }
+
+    head.into_iter()
+        .chain(tail.into_iter().rev())
+        .enumerate()
+        .map(|(position, node)| (node, position))
+        .collect()
+}
+
+/// The packages of a graph, identified by their index in alphabetical order.
+struct IndexedGraph<'a> {
+    names: Vec<&'a str>,
+    /// The dependencies of each package, sorted and limited to the packages of the graph.
+    edges: Vec<Vec<usize>>,
+}
+
+impl<'a> IndexedGraph<'a> {
+    fn new(graph: &'a Graph) -> Self {
+        let mut names: Vec<&str> = graph.adjacency_list.keys().map(|k| k.as_str()).collect();
+        names.sort();
+
+        let edges = names
+            .iter()
+            .map(|name| {
+                let mut dependencies: Vec<usize> = graph.adjacency_list[*name]
+                    .iter()
+                    .filter_map(|dep| names.binary_search(&dep.as_str()).ok())
+                    .collect();
+                dependencies.sort();
+                dependencies.dedup();
+                dependencies
+            })
+            .collect();
+
+        IndexedGraph { names, edges }
+    }
+
+    /// Returns the strongly connected components, using Tarjan's algorithm.
+    fn strongly_connected_components(&self) -> Vec<Vec<usize>> {
+        let mut tarjan = Tarjan {
+            graph: self,
+            index: 0,
+            indices: vec![None; self.names.len()],
+            low_links: vec![0; self.names.len()],
+            on_stack: vec![false; self.names.len()],
+            stack: Vec::new(),
+            components: Vec::new(),
+        };
+        for node in 0..self.names.len() {
+            if tarjan.indices[node].is_none() {
+                tarjan.visit(node);
+            }
+        }
+        tarjan.components
+    }
+}
+
+struct Tarjan<'g, 'a> {
+    graph: &'g IndexedGraph<'a>,
+    index: usize,
+    indices: Vec<Option<usize>>,
+    low_links: Vec<usize>,
+    on_stack: Vec<bool>,
+    stack: Vec<usize>,
+    components: Vec<Vec<usize>>,
+}
+
+impl Tarjan<'_, '_> {
+    fn visit(&mut self, node: usize) {
+        self.indices[node] = Some(self.index);
+        self.low_links[node] = self.index;
+        self.index += 1;
+        self.stack.push(node);
+        self.on_stack[node] = true;
+
+        for &dep in &self.graph.edges[node] {
+            match self.indices[dep] {
+                None => {
+                    self.visit(dep);
+                    self.low_links[node] = self.low_links[node].min(self.low_links[dep]);
+                }
+                Some(index) if self.on_stack[dep] => {
+                    self.low_links[node] = self.low_links[node].min(index);
+                }
+                Some(_) => {}
+            }
+        }
+
+        // the node is the root of a component, which consists of the nodes above it on the stack
+        if Some(self.low_links[node]) == self.indices[node] {
+            let mut component = Vec::new();
+            while let Some(member) = self.stack.pop() {
+                self.on_stack[member] = false;
+                component.push(member);
+                if member == node {
+                    break;
+                }
+            }
+            self.components.push(component);
+        }
+    }
+}
+
+/// The state of Johnson's algorithm, which finds the elementary cycles through `start` within a component.
+/// Only the packages of the component which come after `start` are visited, so every cycle is found exactly once,
+/// starting at its first package.
+struct CycleSearch<'g, 'a> {
+    graph: &'g IndexedGraph<'a>,
+    limit: usize,
+    component: Vec<usize>,
+    start: usize,
+    blocked: Vec<bool>,
+    blocked_by: Vec<Vec<usize>>,
+    stack: Vec<usize>,
+    cycles: Vec<Vec<usize>>,
+    truncated: bool,
+}
+
+impl CycleSearch<'_, '_> {
+    /// Returns whether a cycle was found from `node` back to `start`.
+    fn circuit(&mut self, node: usize) -> bool {
+        let mut found = false;
+        self.stack.push(node);
+        self.blocked[node] = true;
+
+        for &dep in &self.graph.edges[node] {
+            if self.truncated {
+                break;
+            }
+            if dep < self.start || self.component.binary_search(&dep).is_err() {
+                continue;
+            }
+            if dep == self.start {
+                if self.cycles.len() == self.limit {
+                    self.truncated = true;
+                    break;
+                }
+                self.cycles.push(self.stack.clone());
+                found = true;
+            } else if !self.blocked[dep] && self.circuit(dep) {
+                found = true;
+            }
+        }
+
+        if found {
+            self.unblock(node);
+        } else {
+            for &dep in &self.graph.edges[node] {
+                if !self.blocked_by[dep].contains(&node) {
+                    self.blocked_by[dep].push(node);
+                }
+            }
+        }
+
+        self.stack.pop();
+        found
+    }
+
+    fn unblock(&mut self, node: usize) {
+        self.blocked[node] = false;
+        while let Some(blocking) = self.blocked_by[node].pop() {
+            if self.blocked[blocking] {
+                self.unblock(blocking);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph(edges: &[(&str, &str)]) -> Graph {
+        let mut graph = Graph::new();
+        for (from, to) in edges {
+            graph.add_edge(from, to);
+            graph.adjacency_list.entry(to.to_string()).or_default();
+        }
+        graph
+    }
+
+    fn edges(analysis: &CycleAnalysis) -> Vec<(&str, &str)> {
+        analysis
+            .edges
+            .iter()
+            .map(|(from, to)| (from.as_str(), to.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn test_no_cycles() {
+        let analysis =
+            graph(&[("A", "B"), ("B", "C"), ("C", "D")]).analyze_cycles(DEFAULT_CYCLE_LIMIT);
+
+        assert!(analysis.is_empty());
+        assert!(analysis.edges.is_empty());
+    }
+
+    #[test]
+    fn test_single_cycle() {
+        let analysis =
+            graph(&[("A", "B"), ("B", "C"), ("C", "A")]).analyze_cycles(DEFAULT_CYCLE_LIMIT);
+
+        assert_eq!(analysis.cycles, vec![vec!["A", "B", "C"]]);
+        assert_eq!(edges(&analysis), vec![("A", "B"), ("B", "C"), ("C", "A")]);
+    }
+
+    #[test]
+    fn test_multiple_cycles() {
+        let analysis = graph(&[
+            ("A", "B"),
+            ("B", "C"),
+            ("C", "A"),
+            ("D", "E"),
+            ("E", "F"),
+            ("F", "D"),
+        ])
+        .analyze_cycles(DEFAULT_CYCLE_LIMIT);
+
+        assert_eq!(
+            analysis.cycles,
+            vec![vec!["A", "B", "C"], vec!["D", "E", "F"]]
+        );
+        assert_eq!(analysis.edges.len(), 6);
+    }
+
+    #[test]
+    fn test_disconnected_graph_with_cycle() {
+        let analysis = graph(&[("A", "B"), ("B", "C"), ("C", "A"), ("D", "E")])
+            .analyze_cycles(DEFAULT_CYCLE_LIMIT);
+
+        assert!(analysis.contains_edge("C", "A"));
+        assert!(!analysis.contains_edge("D", "E"));
+    }
+
+    #[test]
+    fn test_graph_with_self_loop() {
+        let analysis = graph(&[("A", "A"), ("B", "C")]).analyze_cycles(DEFAULT_CYCLE_LIMIT);
+
+        assert_eq!(analysis.cycles, vec![vec!["A"]]);
+        assert_eq!(edges(&analysis), vec![("A", "A")]);
+    }
+
+    #[test]
+    fn test_complex_graph_with_mixed_cycles() {
+        let analysis = graph(&[
+            ("A", "B"),
+            ("B", "C"),
+            ("C", "A"),
+            ("B", "D"),
+            ("D", "E"),
+            ("E", "B"),
+            ("F", "G"),
+        ])
+        .analyze_cycles(DEFAULT_CYCLE_LIMIT);
+
+        assert_eq!(
+            edges(&analysis),
+            vec![
+                ("A", "B"),
+                ("B", "C"),
+                ("B", "D"),
+                ("C", "A"),
+                ("D", "E"),
+                ("E", "B")
+            ]
+        );
+        assert!(!analysis.contains_edge("F", "G"));
+    }
+
+    #[test]
+    fn test_ignore_edges_to_other_packages() {
+        let mut graph = graph(&[("A", "B"), ("B", "A")]);
+        graph.add_edge("A", "serde");
+        graph.add_edge("serde", "A");
+        graph.adjacency_list.remove("serde");
+
+        let analysis = graph.analyze_cycles(DEFAULT_CYCLE_LIMIT);
+        assert_eq!(analysis.cycles, vec![vec!["A", "B"]]);
+    }
+
+    #[test]
+    fn test_cycles_sharing_visited_packages() {
+        // a single DFS reaches `C` via `B` first, and misses the cycle `A → C → A`
+        let analysis = graph(&[("A", "B"), ("A", "C"), ("B", "C"), ("C", "A")])
+            .analyze_cycles(DEFAULT_CYCLE_LIMIT);
+
+        assert_eq!(analysis.components, vec![vec!["A", "B", "C"]]);
+        assert_eq!(analysis.cycles, vec![vec!["A", "B", "C"], vec!["A", "C"]]);
+        assert!(analysis.contains_edge("A", "C"));
+        assert!(!analysis.truncated);
+    }
+
+    #[test]
+    fn test_cycles_after_the_first_one_found() {
+        // a DFS which stops at the first cycle through `A` misses the cycle `A → C → A`
+        let analysis = graph(&[("A", "B"), ("B", "A"), ("A", "C"), ("C", "A")])
+            .analyze_cycles(DEFAULT_CYCLE_LIMIT);
+
+        assert_eq!(analysis.cycles, vec![vec!["A", "B"], vec!["A", "C"]]);
+        assert_eq!(
+            edges(&analysis),
+            vec![("A", "B"), ("A", "C"), ("B", "A"), ("C", "A")]
+        );
+    }
+
+    #[test]
+    fn test_components() {
+        let analysis = graph(&[
+            ("A", "B"),
+            ("B", "A"),
+            ("B", "C"),
+            ("C", "D"),
+            ("D", "C"),
+            ("E", "E"),
+            ("F", "A"),
+        ])
+        .analyze_cycles(DEFAULT_CYCLE_LIMIT);
+
+        assert_eq!(
+            analysis.components,
+            vec![vec!["A", "B"], vec!["C", "D"], vec!["E"]]
+        );
+        assert_eq!(analysis.component_of("D"), Some(1));
+        assert_eq!(analysis.component_of("F"), None);
+        // the edge between two components is not part of a cycle
+        assert!(!analysis.contains_edge("B", "C"));
+    }
+
+    #[test]
+    fn test_cycle_limit() {
+        // every pair of packages depends on each other
+        let mut edges = Vec::new();
+        for from in ["A", "B", "C", "D"] {
+            for to in ["A", "B", "C", "D"] {
+                if from != to {
+                    edges.push((from, to));
+                }
+            }
+        }
+        let graph = graph(&edges);
+
+        let all = graph.analyze_cycles(DEFAULT_CYCLE_LIMIT);
+        assert_eq!(all.cycles.len(), 20);
+        assert!(!all.truncated);
+
+        let limited = graph.analyze_cycles(5);
+        assert_eq!(limited.cycles, all.cycles[..5]);
+        assert!(limited.truncated);
+        assert_eq!(limited.edges, all.edges);
+
+        assert!(!graph.analyze_cycles(20).truncated);
+    }
+
+    #[test]
+    fn test_cycle_text() {
+        let cycle = vec!["a".to_string(), "b".to_string()];
+        assert_eq!(cycle_text(&cycle), "a → b → a");
+    }
+
+    fn feedback_edges(analysis: &CycleAnalysis) -> Vec<(&str, &str, usize)> {
+        analysis
+            .feedback_arc_set
+            .iter()
+            .map(|edge| (edge.from.as_str(), edge.to.as_str(), edge.cycles))
+            .collect()
+    }
+
+    #[test]
+    fn test_minimum_feedback_arc_set() {
+        // `B → C` is part of every cycle, so removing it alone is enough
+        let analysis = graph(&[
+            ("A", "B"),
+            ("B", "C"),
+            ("C", "A"),
+            ("C", "D"),
+            ("D", "B"),
+            ("E", "E"),
+        ])
+        .analyze_cycles(DEFAULT_CYCLE_LIMIT);
+
+        assert_eq!(
+            feedback_edges(&analysis),
+            vec![("B", "C", 2), ("E", "E", 1)]
+        );
+        assert_eq!(analysis.feedback_arc_set[1].component, 1);
+    }
+
+    #[test]
+    fn test_heuristic_feedback_arc_set() {
+        // a ring of twelve packages, each depending on the next two, has too many edges to search exhaustively
+        let names: Vec<String> = (0..12).map(|i| format!("P{:02}", i)).collect();
+        let mut edges = Vec::new();
+        for i in 0..12 {
+            edges.push((names[i].as_str(), names[(i + 1) % 12].as_str()));
+            edges.push((names[i].as_str(), names[(i + 2) % 12].as_str()));
+        }
+        let graph = graph(&edges);
+        let analysis = graph.analyze_cycles(DEFAULT_CYCLE_LIMIT);
+        assert_eq!(analysis.components.len(), 1);
+
+        // removing the suggested edges makes the graph acyclic, and none of them can be kept
+        let is_acyclic_without = |skipped: &[&FeedbackEdge]| {
+            let mut acyclic = Graph::new();
+            for (from, to) in &edges {
+                acyclic.adjacency_list.entry(to.to_string()).or_default();
+                if !skipped
+                    .iter()
+                    .any(|edge| edge.from == *from && edge.to == *to)
+                {
+                    acyclic.add_edge(from, to);
+                }
+            }
+            acyclic.analyze_cycles(DEFAULT_CYCLE_LIMIT).is_empty()
+        };
+        let suggested: Vec<&FeedbackEdge> = analysis.feedback_arc_set.iter().collect();
+        assert!(is_acyclic_without(&suggested));
+        for i in 0..suggested.len() {
+            let mut fewer = suggested.clone();
+            fewer.remove(i);
+            assert!(!is_acyclic_without(&fewer));
+        }
+        // the ring is cut between the last and the first package
+        assert_eq!(suggested.len(), 3);
+    }
+
+    #[test]
+    fn test_combinations() {
+        assert_eq!(
+            combinations(4, 2).collect::<Vec<_>>(),
+            vec![
+                vec![0, 1],
+                vec![0, 2],
+                vec![0, 3],
+                vec![1, 2],
+                vec![1, 3],
+                vec![2, 3]
+            ]
+        );
+        assert_eq!(
+            combinations(2, 0).collect::<Vec<_>>(),
+            vec![Vec::<usize>::new()]
+        );
+        assert_eq!(combinations(1, 2).count(), 0);
+    }
+}
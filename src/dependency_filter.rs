@@ -1,4 +1,5 @@
-use crate::graph::Graph;
+use crate::graph::{DependencyKind, EdgeInfo, Graph};
+use crate::platform::Platform;
 use std::collections::HashSet;
 
 impl Graph {
@@ -18,9 +19,112 @@ impl Graph {
             })
             .collect();
 
+        let filtered_edge_infos = self
+            .edge_infos
+            .iter()
+            .filter(|((_, to), _)| node_set.contains(to))
+            .map(|(edge, infos)| (edge.clone(), infos.clone()))
+            .collect();
+
         Graph {
             adjacency_list: filtered_adjacency_list,
+            edge_infos: filtered_edge_infos,
+            features: self.features.clone(),
+            external_paths: self.external_paths.clone(),
+            groups: self.groups.clone(),
+        }
+    }
+
+    /// Keeps only the given packages, and with `with_dependencies` also the packages they transitively depend on.
+    /// Edges to the packages which are left out are removed, while edges to external packages are kept.
+    /// Like `cargo build -p`, the dev-dependencies of the dependencies are not followed, unless `follow_dev` is set.
+    pub fn filter_by_packages(
+        &self,
+        packages: &[String],
+        with_dependencies: bool,
+        follow_dev: bool,
+    ) -> Graph {
+        let mut selected: HashSet<&str> = packages
+            .iter()
+            .map(|package| package.as_str())
+            .filter(|package| self.adjacency_list.contains_key(*package))
+            .collect();
+
+        if with_dependencies {
+            // the dev-dependencies of the given packages are followed, as they are part of their build scope
+            let mut stack: Vec<(&str, bool)> =
+                selected.iter().map(|package| (*package, true)).collect();
+            while let Some((package, is_given)) = stack.pop() {
+                for dep in &self.adjacency_list[package] {
+                    let skip_dev = !is_given && !follow_dev;
+                    if skip_dev && self.get_edge_kind(package, dep) == DependencyKind::Dev {
+                        continue;
+                    }
+                    if self.adjacency_list.contains_key(dep) && selected.insert(dep) {
+                        stack.push((dep, false));
+                    }
+                }
+            }
+        }
+
+        let mut filtered = self.filter_edge_infos(|_, to, _| {
+            !self.adjacency_list.contains_key(to) || selected.contains(to)
+        });
+        filtered
+            .adjacency_list
+            .retain(|package, _| selected.contains(package.as_str()));
+        filtered
+            .edge_infos
+            .retain(|(from, _), _| selected.contains(from.as_str()));
+        filtered
+            .features
+            .retain(|package, _| selected.contains(package.as_str()));
+        filtered
+            .external_paths
+            .retain(|package| selected.contains(package.as_str()));
+        filtered
+            .groups
+            .retain(|node, _| selected.contains(node.as_str()));
+        filtered
+    }
+
+    /// Keeps only the edges which were declared with one of the given kinds.
+    /// All packages are kept, even if they lose all their edges.
+    pub fn filter_by_kinds(&self, kinds: &[DependencyKind]) -> Graph {
+        self.filter_edge_infos(|_, _, info| kinds.contains(&info.kind))
+    }
+
+    /// Keeps only the edges which apply to the given platform.
+    /// Dependencies without a `[target.<spec>]` table apply to every platform.
+    pub fn filter_by_platform(&self, platform: &Platform) -> Graph {
+        self.filter_edge_infos(|_, _, info| {
+            info.target
+                .as_deref()
+                .is_none_or(|spec| platform.matches(spec))
+        })
+    }
+
+    /// Keeps only the edge details matching the predicate.
+    /// Edges without any remaining details are removed.
+    pub(crate) fn filter_edge_infos(&self, keep: impl Fn(&str, &str, &EdgeInfo) -> bool) -> Graph {
+        let mut filtered = Graph::new();
+        filtered.features = self.features.clone();
+        filtered.external_paths = self.external_paths.clone();
+        filtered.groups = self.groups.clone();
+
+        for (package, dependencies) in &self.adjacency_list {
+            filtered.adjacency_list.insert(package.clone(), Vec::new());
+
+            for dep in dependencies {
+                for info in self.get_edge_infos(package, dep) {
+                    if keep(package, dep, info) {
+                        filtered.add_edge_with_info(package, dep, info.clone());
+                    }
+                }
+            }
         }
+
+        filtered
     }
 }
 
@@ -42,6 +146,7 @@ mod tests {
             ]
             .into_iter()
             .collect(),
+            ..Graph::new()
         };
 
         let expected_filtered_graph = Graph {
@@ -53,6 +158,7 @@ mod tests {
             ]
             .into_iter()
             .collect(),
+            ..Graph::new()
         };
 
         let filtered_graph = original_graph.filter_dependencies();
@@ -76,9 +182,130 @@ mod tests {
             ]
             .into_iter()
             .collect(),
+            ..Graph::new()
         };
 
         let filtered_graph = graph.filter_dependencies();
         assert_eq!(filtered_graph, graph); // No filtering needed, should be identical
     }
+
+    #[test]
+    fn test_filter_by_kinds() {
+        let mut graph = Graph::new();
+        graph.add_edge_with_info(
+            "A",
+            "B",
+            EdgeInfo {
+                kind: DependencyKind::Normal,
+                ..Default::default()
+            },
+        );
+        graph.add_edge_with_info(
+            "A",
+            "C",
+            EdgeInfo {
+                kind: DependencyKind::Dev,
+                ..Default::default()
+            },
+        );
+        graph.add_edge_with_info(
+            "C",
+            "A",
+            EdgeInfo {
+                kind: DependencyKind::Build,
+                ..Default::default()
+            },
+        );
+        graph.add_edge("C", "B");
+
+        let normal_only = graph.filter_by_kinds(&[DependencyKind::Normal]);
+        assert_eq!(normal_only.adjacency_list["A"], vec!["B"]);
+        assert_eq!(normal_only.adjacency_list["C"], vec!["B"]);
+
+        let normal_and_build =
+            graph.filter_by_kinds(&[DependencyKind::Normal, DependencyKind::Build]);
+        assert_eq!(normal_and_build.adjacency_list["A"], vec!["B"]);
+        assert_eq!(normal_and_build.adjacency_list["C"], vec!["A", "B"]);
+
+        let all = graph.filter_by_kinds(&DependencyKind::ALL);
+        assert_eq!(all.adjacency_list["A"], vec!["B", "C"]);
+        assert_eq!(all.get_edge_kind("A", "C"), DependencyKind::Dev);
+    }
+
+    #[test]
+    fn test_filter_by_platform() {
+        let mut graph = Graph::new();
+        graph.add_edge("app", "core");
+        graph.add_edge_with_info(
+            "app",
+            "sys-unix",
+            EdgeInfo {
+                target: Some("cfg(unix)".to_string()),
+                ..Default::default()
+            },
+        );
+        graph.add_edge_with_info(
+            "app",
+            "sys-windows",
+            EdgeInfo {
+                target: Some("cfg(windows)".to_string()),
+                ..Default::default()
+            },
+        );
+
+        let linux = graph.filter_by_platform(&Platform::from_triple("x86_64-unknown-linux-gnu"));
+        assert_eq!(linux.adjacency_list["app"], vec!["core", "sys-unix"]);
+
+        let windows = graph.filter_by_platform(&Platform::from_triple("x86_64-pc-windows-msvc"));
+        assert_eq!(windows.adjacency_list["app"], vec!["core", "sys-windows"]);
+    }
+
+    #[test]
+    fn test_filter_by_packages() {
+        let mut graph = Graph::new();
+        graph.add_edge("cli", "core");
+        graph.add_edge("cli", "serde");
+        graph.add_edge("core", "util");
+        graph.add_edge("util", "serde");
+        graph.add_edge("bench", "core");
+
+        let cli = graph.filter_by_packages(&["cli".to_string()], false, false);
+        assert_eq!(cli.get_node_count(), 1);
+        assert_eq!(cli.adjacency_list["cli"], vec!["serde"]);
+
+        let with_dependencies = graph.filter_by_packages(&["cli".to_string()], true, false);
+        let mut packages: Vec<&String> = with_dependencies.adjacency_list.keys().collect();
+        packages.sort();
+        assert_eq!(packages, vec!["cli", "core", "util"]);
+        assert_eq!(
+            with_dependencies.adjacency_list["cli"],
+            vec!["core", "serde"]
+        );
+        assert_eq!(with_dependencies.adjacency_list["util"], vec!["serde"]);
+    }
+
+    #[test]
+    fn test_filter_by_packages_skips_transitive_dev_dependencies() {
+        let dev = || EdgeInfo {
+            kind: DependencyKind::Dev,
+            ..Default::default()
+        };
+        let mut graph = Graph::new();
+        graph.add_edge_with_info("cli", "test-utils", dev());
+        graph.add_edge("cli", "core");
+        graph.add_edge_with_info("core", "bench-utils", dev());
+        for package in ["test-utils", "bench-utils"] {
+            graph.adjacency_list.entry(package.to_string()).or_default();
+        }
+
+        let build_scope = graph.filter_by_packages(&["cli".to_string()], true, false);
+        let mut packages: Vec<&String> = build_scope.adjacency_list.keys().collect();
+        packages.sort();
+        assert_eq!(packages, vec!["cli", "core", "test-utils"]);
+        assert!(build_scope.adjacency_list["core"].is_empty());
+
+        let with_dev = graph.filter_by_packages(&["cli".to_string()], true, true);
+        assert!(with_dev.adjacency_list.contains_key("bench-utils"));
+        assert_eq!(with_dev.adjacency_list["core"], vec!["bench-utils"]);
+    }
 }
@@ -0,0 +1,177 @@
+use crate::cycles::CycleAnalysis;
+use crate::diagram_creation::{edge_labels, tangles};
+use crate::graph::{DependencyKind, Graph};
+
+/// Generates the graph in the DOT language of Graphviz.
+/// Edges which are part of a circular dependency are drawn in red, external path packages with a dashed box.
+/// The packages of each strongly connected component are grouped in a cluster, while the nodes of a condensed graph
+/// list the packages they consist of.
+pub fn generate_dot(graph: &Graph, cycles: &CycleAnalysis) -> String {
+    let mut diagram = String::from("digraph workspace {\n    node [shape=box];\n");
+    let adjacent_list = &graph.adjacency_list;
+
+    // Sort package names alphabetically
+    let mut package_names: Vec<&String> = adjacent_list.keys().collect();
+    package_names.sort();
+
+    // Every package is declared as a node, so packages without any edges are shown as well
+    for pkg in package_names.iter() {
+        if let Some(members) = graph.get_group_members(pkg) {
+            diagram.push_str(&format!(
+                "    {} [label=\"{}\\n{}\", style=rounded];\n",
+                quote(pkg),
+                escape(pkg),
+                escape(&members.join(", "))
+            ));
+        } else if graph.is_external_path(pkg) {
+            diagram.push_str(&format!("    {} [style=dashed];\n", quote(pkg)));
+        } else {
+            diagram.push_str(&format!("    {};\n", quote(pkg)));
+        }
+    }
+
+    for (i, component) in tangles(cycles) {
+        diagram.push_str(&format!(
+            "    subgraph cluster_scc_{0} {{\n        label=\"Component {0}\";\n        style=dashed;\n        color=red;\n",
+            i + 1
+        ));
+        for pkg in component {
+            diagram.push_str(&format!("        {};\n", quote(pkg)));
+        }
+        diagram.push_str("    }\n");
+    }
+
+    for pkg in package_names.iter() {
+        for dep in &adjacent_list[*pkg] {
+            if adjacent_list.contains_key(dep) {
+                let mut attributes = edge_attributes(graph, pkg, dep);
+                if cycles.contains_edge(pkg, dep) {
+                    attributes.push("color=red".to_string());
+                    attributes.push("penwidth=2".to_string());
+                }
+                diagram.push_str(&format!("    {} -> {}", quote(pkg), quote(dep)));
+                if !attributes.is_empty() {
+                    diagram.push_str(&format!(" [{}]", attributes.join(", ")));
+                }
+                diagram.push_str(";\n");
+            }
+        }
+    }
+
+    diagram.push_str("}\n");
+    diagram
+}
+
+/// Returns the attributes used to draw the edge from `from` to `to`, matching the styles of the Mermaid diagram.
+/// Dev and optional dependencies are drawn dashed, build dependencies bold.
+fn edge_attributes(graph: &Graph, from: &str, to: &str) -> Vec<String> {
+    let mut attributes = Vec::new();
+    if graph.is_edge_optional(from, to) {
+        attributes.push("style=dashed".to_string());
+    } else {
+        match graph.get_edge_kind(from, to) {
+            DependencyKind::Normal => {}
+            DependencyKind::Build => attributes.push("style=bold".to_string()),
+            DependencyKind::Dev => attributes.push("style=dashed".to_string()),
+        }
+    }
+
+    let labels = edge_labels(graph, from, to);
+    if !labels.is_empty() {
+        attributes.push(format!("label={}", quote(&labels.join(", "))));
+    }
+    attributes
+}
+
+/// Quotes an identifier, as package names may contain characters like `-`.
+fn quote(id: &str) -> String {
+    format!("\"{}\"", escape(id))
+}
+
+/// Escapes the characters which have a special meaning within a quoted string.
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cycles::DEFAULT_CYCLE_LIMIT;
+    use crate::graph::EdgeInfo;
+
+    #[test]
+    fn test_standalone_node() {
+        let mut g = Graph::new();
+        g.adjacency_list.insert("package_a".to_string(), vec![]);
+        let diagram = generate_dot(&g, &g.analyze_cycles(DEFAULT_CYCLE_LIMIT));
+        let expected = "digraph workspace {\n    node [shape=box];\n    \"package_a\";\n}\n";
+        assert_eq!(diagram, expected);
+    }
+
+    #[test]
+    fn test_external_path_node() {
+        let mut g = Graph::new();
+        g.add_edge("app", "shared");
+        g.adjacency_list.insert("shared".to_string(), vec![]);
+        g.external_paths.insert("shared".to_string());
+
+        let diagram = generate_dot(&g, &g.analyze_cycles(DEFAULT_CYCLE_LIMIT));
+        assert!(diagram.contains("    \"app\";\n"));
+        assert!(diagram.contains("    \"shared\" [style=dashed];\n"));
+    }
+
+    #[test]
+    fn test_edge_styles() {
+        let mut g = Graph::new();
+        g.adjacency_list.insert("core".to_string(), vec![]);
+        g.adjacency_list.insert("sys".to_string(), vec![]);
+        g.add_edge("app", "core");
+        g.add_edge_with_info(
+            "app",
+            "sys",
+            EdgeInfo {
+                kind: DependencyKind::Build,
+                target: Some("cfg(target_os = \"linux\")".to_string()),
+                ..Default::default()
+            },
+        );
+
+        let diagram = generate_dot(&g, &g.analyze_cycles(DEFAULT_CYCLE_LIMIT));
+        assert!(diagram.contains("    \"app\" -> \"core\";\n"));
+        assert!(diagram.contains(
+            "    \"app\" -> \"sys\" [style=bold, label=\"build, cfg(target_os = \\\"linux\\\")\"];\n"
+        ));
+    }
+
+    #[test]
+    fn test_cycle_highlighting() {
+        let mut g = Graph::new();
+        g.add_edge("a", "b");
+        g.add_edge("b", "a");
+        g.add_edge("b", "c");
+        g.adjacency_list.insert("c".to_string(), vec![]);
+
+        let diagram = generate_dot(&g, &g.analyze_cycles(DEFAULT_CYCLE_LIMIT));
+        assert!(diagram.contains("\"a\" -> \"b\" [color=red, penwidth=2];"));
+        assert!(diagram.contains("\"b\" -> \"a\" [color=red, penwidth=2];"));
+        assert!(diagram.contains("    \"b\" -> \"c\";\n"));
+        assert!(diagram.contains(
+            "    subgraph cluster_scc_1 {\n        label=\"Component 1\";\n        style=dashed;\n        color=red;\n        \"a\";\n        \"b\";\n    }\n"
+        ));
+    }
+
+    #[test]
+    fn test_condensed_graph() {
+        let mut g = Graph::new();
+        g.add_edge("app", "a");
+        g.add_edge("a", "b");
+        g.add_edge("b", "a");
+        let g = g.condense(&g.analyze_cycles(DEFAULT_CYCLE_LIMIT));
+
+        let diagram = generate_dot(&g, &g.analyze_cycles(DEFAULT_CYCLE_LIMIT));
+        assert!(diagram
+            .contains("    \"Component 1\" [label=\"Component 1\\na, b\", style=rounded];\n"));
+        assert!(diagram.contains("    \"app\" -> \"Component 1\";\n"));
+        assert!(!diagram.contains("cluster"));
+    }
+}
@@ -1,6 +1,18 @@
-use crate::graph::Graph;
-// Function to generate the component diagram in Mermaid format
-pub fn generate_mermaid_markdown(graph: &Graph) -> String {
+use crate::cycles::CycleAnalysis;
+use crate::diagram_creation::{edge_labels, tangles};
+use crate::graph::{DependencyKind, Graph};
+use crate::layers::{layers, Levels};
+use std::collections::HashSet;
+
+/// Generates the component diagram in Mermaid format.
+/// Edges which are part of a circular dependency are marked with the class `red`, and the packages of each strongly
+/// connected component are grouped in a subgraph. The nodes of a condensed graph are drawn as subgraphs as well.
+/// If the levels of the packages are given, the packages are grouped by layer instead.
+pub fn generate_mermaid_markdown(
+    graph: &Graph,
+    cycles: &CycleAnalysis,
+    levels: Option<&Levels>,
+) -> String {
     let mut diagram = String::from("graph TD\n");
     let adjacent_list = &graph.adjacency_list;
 
@@ -9,36 +21,174 @@ pub fn generate_mermaid_markdown(graph: &Graph) -> String {
     package_names.sort();
 
     // Set to track packages that are already referenced in edges
-    let mut referenced_packages = std::collections::HashSet::new();
+    let mut referenced_packages = HashSet::new();
 
     // Generate edges for packages in sorted order
     for pkg in package_names.iter() {
         if let Some(deps) = adjacent_list.get(*pkg) {
             for dep in deps {
                 if adjacent_list.contains_key(dep) {
-                    diagram.push_str(&format!("    {} --> {}\n", pkg, dep));
+                    let arrow = edge_arrow(graph, pkg, dep);
+                    let class = if cycles.contains_edge(pkg, dep) {
+                        ":::red"
+                    } else {
+                        ""
+                    };
+                    diagram.push_str(&format!(
+                        "    {} {} {}{}\n",
+                        node_id(pkg),
+                        arrow,
+                        node_id(dep),
+                        class
+                    ));
                     referenced_packages.insert(dep.clone());
                 }
             }
         }
     }
 
-    // Add standalone nodes for packages with no dependencies and not referenced in any edges
-    for pkg in package_names.iter() {
-        if let Some(deps) = adjacent_list.get(*pkg) {
-            if deps.is_empty() && !referenced_packages.contains(*pkg) {
-                diagram.push_str(&format!("    {}\n", pkg));
+    match levels {
+        Some(levels) => diagram.push_str(&generate_layers(graph, cycles, levels)),
+        None => {
+            // Add standalone nodes for packages with no dependencies and not referenced in any edges
+            for pkg in package_names.iter() {
+                if let Some(deps) = adjacent_list.get(*pkg) {
+                    if deps.is_empty()
+                        && !referenced_packages.contains(*pkg)
+                        && graph.get_group_members(pkg).is_none()
+                    {
+                        diagram.push_str(&format!("    {}\n", pkg));
+                    }
+                }
+            }
+
+            for (node, members) in &graph.groups {
+                diagram.push_str(&subgraph(&node_id(node), node, members, 1));
+            }
+
+            for (i, component) in tangles(cycles) {
+                diagram.push_str(&subgraph(
+                    &format!("scc_{}", i + 1),
+                    &format!("Component {}", i + 1),
+                    component,
+                    1,
+                ));
+            }
+        }
+    }
+
+    if !cycles.edges.is_empty() {
+        diagram.push_str("classDef red stroke:#ff0000,stroke-width:2px;\n");
+    }
+    diagram.push_str(&generate_external_path_classes(graph));
+
+    diagram
+}
+
+/// Returns one subgraph per layer, which contains the packages of the layer. The nodes of a condensed graph and the
+/// packages which depend on each other are nested in a subgraph within their layer.
+fn generate_layers(graph: &Graph, cycles: &CycleAnalysis, levels: &Levels) -> String {
+    let mut diagram = String::new();
+    let mut nested = HashSet::new();
+
+    for (level, packages) in layers(levels).iter().enumerate() {
+        diagram.push_str(&format!("    subgraph level_{0}[\"Level {0}\"]\n", level));
+        for pkg in packages {
+            let tangle = cycles
+                .component_of(pkg)
+                .filter(|&i| cycles.components[i].len() > 1);
+            if let Some(members) = graph.get_group_members(pkg) {
+                diagram.push_str(&subgraph(&node_id(pkg), pkg, members, 2));
+            } else if let Some(i) = tangle {
+                if nested.insert(i) {
+                    diagram.push_str(&subgraph(
+                        &format!("scc_{}", i + 1),
+                        &format!("Component {}", i + 1),
+                        &cycles.components[i],
+                        2,
+                    ));
+                }
+            } else {
+                diagram.push_str(&format!("        {}\n", node_id(pkg)));
             }
         }
+        diagram.push_str("    end\n");
     }
 
     diagram
 }
 
+/// Returns a subgraph with the given packages, indented by the given depth.
+fn subgraph(id: &str, title: &str, packages: &[String], depth: usize) -> String {
+    let indent = "    ".repeat(depth);
+    let mut diagram = format!("{}subgraph {}[\"{}\"]\n", indent, id, title);
+    for pkg in packages {
+        diagram.push_str(&format!("{}    {}\n", indent, pkg));
+    }
+    diagram.push_str(&format!("{}end\n", indent));
+    diagram
+}
+
+/// Returns the identifier of a node, as the names of condensed nodes contain spaces.
+fn node_id(name: &str) -> String {
+    name.replace(
+        |c: char| !c.is_ascii_alphanumeric() && c != '-' && c != '_',
+        "_",
+    )
+}
+
+/// Returns the class definition which marks the external path packages with a dashed, grey box.
+/// The string is empty if there are no such packages.
+fn generate_external_path_classes(graph: &Graph) -> String {
+    let mut packages: Vec<&String> = graph
+        .adjacency_list
+        .keys()
+        .filter(|pkg| graph.is_external_path(pkg))
+        .collect();
+    if packages.is_empty() {
+        return String::new();
+    }
+    packages.sort();
+
+    let names: Vec<String> = packages.iter().map(|pkg| node_id(pkg)).collect();
+    format!(
+        "classDef externalPath fill:#f5f5f5,stroke:#999,stroke-dasharray:4 3;\nclass {} externalPath;\n",
+        names.join(",")
+    )
+}
+
+/// Returns the Mermaid arrow used to draw the edge from `from` to `to`.
+/// Normal dependencies are drawn as plain arrows, the others are styled and labeled.
+/// Optional dependencies are drawn dashed.
+fn edge_arrow(graph: &Graph, from: &str, to: &str) -> String {
+    let arrow = if graph.is_edge_optional(from, to) {
+        "-.->"
+    } else {
+        match graph.get_edge_kind(from, to) {
+            DependencyKind::Normal => "-->",
+            DependencyKind::Build => "==>",
+            DependencyKind::Dev => "-.->",
+        }
+    };
+
+    let labels = edge_labels(graph, from, to);
+    if labels.is_empty() {
+        arrow.to_string()
+    } else {
+        format!(
+            "{}|\"{}\"|",
+            arrow,
+            labels.join(", ").replace('"', "#quot;")
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::commons::{DependencyInfo, Package};
+    use crate::cycles::DEFAULT_CYCLE_LIMIT;
+    use crate::graph::EdgeInfo;
+    use crate::types::commons::{DependencyInfo, DetailedDependency, Package};
     use crate::types::nested::{Manifest, ManifestFinding};
     use std::collections::HashMap;
     use std::path::PathBuf;
@@ -59,6 +209,7 @@ mod tests {
                 } else {
                     Some(dependency_map)
                 },
+                ..Default::default()
             },
         }
     }
@@ -67,7 +218,7 @@ mod tests {
     fn test_single_package_no_dependencies() {
         // Single package, no dependencies
         let g = Graph::new();
-        let diagram = generate_mermaid_markdown(&g);
+        let diagram = generate_mermaid_markdown(&g, &g.analyze_cycles(DEFAULT_CYCLE_LIMIT), None);
         let expected = "graph TD\n";
         assert_eq!(diagram, expected);
     }
@@ -79,8 +230,8 @@ mod tests {
             setup_manifest("package_b", vec![]),
         ];
 
-        let g = Graph::new_from_manifests(&nested);
-        let diagram = generate_mermaid_markdown(&g);
+        let g = Graph::new_from_manifests(&nested, None);
+        let diagram = generate_mermaid_markdown(&g, &g.analyze_cycles(DEFAULT_CYCLE_LIMIT), None);
         let expected = "graph TD\n    package_a --> package_b\n";
         assert_eq!(diagram, expected);
     }
@@ -92,9 +243,161 @@ mod tests {
             setup_manifest("package_b", vec![]),
         ];
 
-        let g = Graph::new_from_manifests(&nested);
-        let diagram = generate_mermaid_markdown(&g);
+        let g = Graph::new_from_manifests(&nested, None);
+        let diagram = generate_mermaid_markdown(&g, &g.analyze_cycles(DEFAULT_CYCLE_LIMIT), None);
         let expected = "graph TD\n    package_a --> package_b\n";
         assert_eq!(diagram, expected);
     }
+
+    #[test]
+    fn test_dependency_kind_styles() {
+        let mut a = setup_manifest("package_a", vec!["package_b"]);
+        a.manifest.dev_dependencies = Some(HashMap::from([(
+            "package_c".to_string(),
+            DependencyInfo::Simple("1.0".to_string()),
+        )]));
+        a.manifest.build_dependencies = Some(HashMap::from([(
+            "package_d".to_string(),
+            DependencyInfo::Simple("1.0".to_string()),
+        )]));
+        let nested = vec![
+            a,
+            setup_manifest("package_b", vec![]),
+            setup_manifest("package_c", vec![]),
+            setup_manifest("package_d", vec![]),
+        ];
+
+        let g = Graph::new_from_manifests(&nested, None);
+        let diagram = generate_mermaid_markdown(&g, &g.analyze_cycles(DEFAULT_CYCLE_LIMIT), None);
+        let expected = "graph TD\n    package_a --> package_b\n    package_a -.->|\"dev\"| package_c\n    package_a ==>|\"build\"| package_d\n";
+        assert_eq!(diagram, expected);
+    }
+
+    #[test]
+    fn test_renamed_dependency_label() {
+        let mut a = setup_manifest("package_a", vec![]);
+        a.manifest.dependencies = Some(HashMap::from([(
+            "b".to_string(),
+            DependencyInfo::Detailed(DetailedDependency {
+                path: Some("../package_b".to_string()),
+                package: Some("package_b".to_string()),
+                ..Default::default()
+            }),
+        )]));
+        let nested = vec![a, setup_manifest("package_b", vec![])];
+
+        let g = Graph::new_from_manifests(&nested, None);
+        let diagram = generate_mermaid_markdown(&g, &g.analyze_cycles(DEFAULT_CYCLE_LIMIT), None);
+        let expected = "graph TD\n    package_a -->|\"as b\"| package_b\n";
+        assert_eq!(diagram, expected);
+    }
+
+    #[test]
+    fn test_platform_label() {
+        let mut g = Graph::new();
+        g.adjacency_list.insert("sys".to_string(), vec![]);
+        g.add_edge_with_info(
+            "app",
+            "sys",
+            EdgeInfo {
+                target: Some("cfg(target_os = \"linux\")".to_string()),
+                ..Default::default()
+            },
+        );
+
+        let diagram = generate_mermaid_markdown(&g, &g.analyze_cycles(DEFAULT_CYCLE_LIMIT), None);
+        let expected = "graph TD\n    app -->|\"cfg(target_os = #quot;linux#quot;)\"| sys\n";
+        assert_eq!(diagram, expected);
+    }
+
+    #[test]
+    fn test_optional_dependency_label() {
+        let mut g = Graph::new();
+        g.adjacency_list.insert("tls".to_string(), vec![]);
+        g.add_edge_with_info(
+            "app",
+            "tls",
+            EdgeInfo {
+                optional: true,
+                ..Default::default()
+            },
+        );
+        g.features.insert(
+            "app".to_string(),
+            HashMap::from([("secure".to_string(), vec!["dep:tls".to_string()])]),
+        );
+
+        let diagram = generate_mermaid_markdown(&g, &g.analyze_cycles(DEFAULT_CYCLE_LIMIT), None);
+        let expected = "graph TD\n    app -.->|\"with feature secure\"| tls\n";
+        assert_eq!(diagram, expected);
+    }
+
+    #[test]
+    fn test_cycle_with_styled_edges() {
+        let mut g = Graph::new();
+        g.add_edge("a", "b");
+        g.add_edge_with_info(
+            "b",
+            "a",
+            EdgeInfo {
+                kind: DependencyKind::Dev,
+                ..Default::default()
+            },
+        );
+        g.add_edge_with_info(
+            "b",
+            "c",
+            EdgeInfo {
+                kind: DependencyKind::Build,
+                ..Default::default()
+            },
+        );
+        g.adjacency_list.insert("c".to_string(), vec![]);
+
+        // Dev and build edges take part in cycles like any other edge
+        let diagram = generate_mermaid_markdown(&g, &g.analyze_cycles(DEFAULT_CYCLE_LIMIT), None);
+        let expected = "graph TD\n    a --> b:::red\n    b -.->|\"dev\"| a:::red\n    b ==>|\"build\"| c\n    subgraph scc_1[\"Component 1\"]\n        a\n        b\n    end\nclassDef red stroke:#ff0000,stroke-width:2px;\n";
+        assert_eq!(diagram, expected);
+    }
+
+    #[test]
+    fn test_standalone_nodes_with_cycle() {
+        let mut g = Graph::new();
+        g.add_edge("a", "b");
+        g.add_edge("b", "a");
+        g.adjacency_list.insert("standalone".to_string(), vec![]);
+
+        let diagram = generate_mermaid_markdown(&g, &g.analyze_cycles(DEFAULT_CYCLE_LIMIT), None);
+        assert!(diagram.contains("    a --> b:::red\n"));
+        assert!(diagram.contains("    standalone\n"));
+    }
+
+    #[test]
+    fn test_condensed_graph() {
+        let mut g = Graph::new();
+        g.add_edge("app", "a");
+        g.add_edge("a", "b");
+        g.add_edge("b", "a");
+        let g = g.condense(&g.analyze_cycles(DEFAULT_CYCLE_LIMIT));
+
+        let diagram = generate_mermaid_markdown(&g, &g.analyze_cycles(DEFAULT_CYCLE_LIMIT), None);
+        let expected = "graph TD\n    app --> Component_1\n    subgraph Component_1[\"Component 1\"]\n        a\n        b\n    end\n";
+        assert_eq!(diagram, expected);
+    }
+
+    #[test]
+    fn test_layers() {
+        let mut g = Graph::new();
+        g.add_edge("app", "a");
+        g.add_edge("a", "b");
+        g.add_edge("b", "a");
+        g.add_edge("b", "core");
+        g.adjacency_list.insert("core".to_string(), vec![]);
+        let cycles = g.analyze_cycles(DEFAULT_CYCLE_LIMIT);
+        let levels = g.calculate_levels(&cycles);
+
+        let diagram = generate_mermaid_markdown(&g, &cycles, Some(&levels));
+        let expected = "graph TD\n    a --> b:::red\n    app --> a\n    b --> a:::red\n    b --> core\n    subgraph level_0[\"Level 0\"]\n        core\n    end\n    subgraph level_1[\"Level 1\"]\n        subgraph scc_1[\"Component 1\"]\n            a\n            b\n        end\n    end\n    subgraph level_2[\"Level 2\"]\n        app\n    end\nclassDef red stroke:#ff0000,stroke-width:2px;\n";
+        assert_eq!(diagram, expected);
+    }
 }
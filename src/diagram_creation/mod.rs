@@ -1,11 +1,58 @@
-use crate::diagram_creation::circle_detector::detect_circular_dependencies;
-use crate::diagram_creation::mermaid_generator::generate_mermaid_markdown;
-use crate::graph::Graph;
+use crate::cycles::CycleAnalysis;
+use crate::graph::{DependencyKind, Graph};
 
-mod circle_detector;
+mod dot_generator;
 mod mermaid_generator;
+mod svg_generator;
 
-pub fn create_diagram(graph: &Graph) -> String {
-    let diagram = generate_mermaid_markdown(graph);
-    detect_circular_dependencies(&diagram)
+pub use dot_generator::generate_dot;
+pub use mermaid_generator::generate_mermaid_markdown;
+pub use svg_generator::{escape_xml, generate_svg};
+
+/// Returns the labels of the edge from `from` to `to`, which are shared by all diagram formats.
+/// Dependencies other than normal ones are labeled with their kind, optional ones with the features enabling them.
+/// Renamed and platform specific dependencies are labeled with their alias and platform.
+fn edge_labels(graph: &Graph, from: &str, to: &str) -> Vec<String> {
+    let kind_label = match graph.get_edge_kind(from, to) {
+        DependencyKind::Normal => None,
+        DependencyKind::Build => Some("build".to_string()),
+        DependencyKind::Dev => Some("dev".to_string()),
+    };
+
+    let mut feature_label = None;
+    if graph.is_edge_optional(from, to) {
+        let features = graph.get_enabling_features(from, to);
+        feature_label = Some(if features.is_empty() {
+            "optional".to_string()
+        } else {
+            format!("with feature {}", features.join(" or "))
+        });
+    }
+
+    kind_label
+        .into_iter()
+        .chain(feature_label)
+        .chain(
+            graph
+                .get_edge_aliases(from, to)
+                .into_iter()
+                .map(|alias| format!("as {}", alias)),
+        )
+        .chain(
+            graph
+                .get_edge_targets(from, to)
+                .into_iter()
+                .map(|target| target.to_string()),
+        )
+        .collect()
+}
+
+/// Returns the strongly connected components with more than one package, together with their index.
+/// Packages which only depend on themselves are not grouped in the diagrams.
+fn tangles(cycles: &CycleAnalysis) -> impl Iterator<Item = (usize, &Vec<String>)> {
+    cycles
+        .components
+        .iter()
+        .enumerate()
+        .filter(|(_, component)| component.len() > 1)
 }
@@ -0,0 +1,440 @@
+use crate::graph::Graph;
+use std::collections::HashMap;
+
+const NODE_HEIGHT: f64 = 36.0;
+const NODE_PADDING: f64 = 24.0;
+const CHAR_WIDTH: f64 = 8.0;
+const DUMMY_WIDTH: f64 = 16.0;
+const NODE_GAP: f64 = 40.0;
+const LAYER_GAP: f64 = 70.0;
+pub const MARGIN: f64 = 20.0;
+const SWEEPS: usize = 8;
+const MUTUAL_OFFSET: f64 = 6.0;
+
+/// The positions of all nodes and edges of a graph, computed by a layered (Sugiyama-style) layout.
+/// Dependents are placed above their dependencies.
+#[derive(Debug, Default)]
+pub struct Layout {
+    pub nodes: Vec<NodeLayout>,
+    pub edges: Vec<EdgeLayout>,
+    pub width: f64,
+    pub height: f64,
+}
+
+/// A package, given by the center of its box.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodeLayout {
+    pub name: String,
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+/// An edge, given as a polyline from the dependent to the dependency.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EdgeLayout {
+    pub from: String,
+    pub to: String,
+    pub points: Vec<(f64, f64)>,
+}
+
+/// A node of the layered graph, which is either a package or a dummy node placed on a layer an edge passes through.
+struct LayerNode {
+    package: Option<usize>,
+    width: f64,
+    predecessors: Vec<usize>,
+    successors: Vec<usize>,
+}
+
+impl Graph {
+    /// Computes a layered layout of the graph in four steps: cycles are broken by reversing edges, packages are assigned
+    /// to layers by the longest path from the top, the order within the layers is improved with barycenter sweeps to
+    /// reduce crossings, and finally the coordinates are assigned.
+    pub fn layout(&self) -> Layout {
+        let mut names: Vec<&String> = self.adjacency_list.keys().collect();
+        names.sort();
+        let index: HashMap<&str, usize> = names
+            .iter()
+            .enumerate()
+            .map(|(i, name)| (name.as_str(), i))
+            .collect();
+
+        // collect the edges between packages of the graph, self-loops are drawn separately
+        let mut edges: Vec<(usize, usize)> = Vec::new();
+        let mut self_loops = Vec::new();
+        for (from, name) in names.iter().enumerate() {
+            for dep in &self.adjacency_list[*name] {
+                if let Some(&to) = index.get(dep.as_str()) {
+                    if from == to {
+                        self_loops.push(from);
+                    } else if !edges.contains(&(from, to)) {
+                        edges.push((from, to));
+                    }
+                }
+            }
+        }
+
+        let reversed = find_back_edges(names.len(), &edges);
+        let acyclic: Vec<(usize, usize)> = edges
+            .iter()
+            .zip(&reversed)
+            .map(|(&(from, to), &reversed)| if reversed { (to, from) } else { (from, to) })
+            .collect();
+        let layers_of = assign_layers(names.len(), &acyclic);
+
+        // build the layered graph, edges spanning several layers pass through dummy nodes
+        let mut nodes: Vec<LayerNode> = names
+            .iter()
+            .enumerate()
+            .map(|(i, name)| LayerNode {
+                package: Some(i),
+                width: name.len() as f64 * CHAR_WIDTH + NODE_PADDING,
+                predecessors: Vec::new(),
+                successors: Vec::new(),
+            })
+            .collect();
+        let mut node_layers = layers_of.clone();
+        let mut chains = Vec::new();
+        for &(upper, lower) in &acyclic {
+            let mut chain = vec![upper];
+            for layer in layers_of[upper] + 1..layers_of[lower] {
+                nodes.push(LayerNode {
+                    package: None,
+                    width: DUMMY_WIDTH,
+                    predecessors: Vec::new(),
+                    successors: Vec::new(),
+                });
+                node_layers.push(layer);
+                chain.push(nodes.len() - 1);
+            }
+            chain.push(lower);
+            for pair in chain.windows(2) {
+                nodes[pair[0]].successors.push(pair[1]);
+                nodes[pair[1]].predecessors.push(pair[0]);
+            }
+            chains.push(chain);
+        }
+
+        let layer_count = layers_of.iter().max().map_or(0, |max| max + 1);
+        let mut layers: Vec<Vec<usize>> = vec![Vec::new(); layer_count];
+        for (node, &layer) in node_layers.iter().enumerate() {
+            layers[layer].push(node);
+        }
+        let layers = order_layers(&nodes, layers);
+
+        // assign the coordinates, every layer is centered horizontally
+        let layer_widths: Vec<f64> = layers
+            .iter()
+            .map(|layer| {
+                layer.iter().map(|&n| nodes[n].width).sum::<f64>()
+                    + NODE_GAP * layer.len().saturating_sub(1) as f64
+            })
+            .collect();
+        let max_width = layer_widths.iter().cloned().fold(0.0, f64::max);
+        let mut positions = vec![(0.0, 0.0); nodes.len()];
+        for (l, layer) in layers.iter().enumerate() {
+            let mut x = MARGIN + (max_width - layer_widths[l]) / 2.0;
+            let y = MARGIN + l as f64 * (NODE_HEIGHT + LAYER_GAP) + NODE_HEIGHT / 2.0;
+            for &n in layer {
+                positions[n] = (x + nodes[n].width / 2.0, y);
+                x += nodes[n].width + NODE_GAP;
+            }
+        }
+
+        let mut layout = Layout {
+            width: max_width + 2.0 * MARGIN,
+            height: (layer_count as f64 * (NODE_HEIGHT + LAYER_GAP) - LAYER_GAP).max(0.0)
+                + 2.0 * MARGIN,
+            ..Default::default()
+        };
+        for (n, node) in nodes.iter().enumerate() {
+            if let Some(package) = node.package {
+                layout.nodes.push(NodeLayout {
+                    name: names[package].to_string(),
+                    x: positions[n].0,
+                    y: positions[n].1,
+                    width: node.width,
+                    height: NODE_HEIGHT,
+                });
+            }
+        }
+        // the extra space on the right is used by self-loops
+        if !self_loops.is_empty() {
+            layout.width += NODE_GAP;
+        }
+
+        for ((&(from, to), chain), &reversed) in edges.iter().zip(&chains).zip(&reversed) {
+            let last = chain.len() - 1;
+            let mut points: Vec<(f64, f64)> = chain
+                .iter()
+                .enumerate()
+                .map(|(i, &n)| {
+                    let (x, y) = positions[n];
+                    match i {
+                        0 => (x, y + NODE_HEIGHT / 2.0),
+                        i if i == last => (x, y - NODE_HEIGHT / 2.0),
+                        _ => (x, y),
+                    }
+                })
+                .collect();
+            if reversed {
+                points.reverse();
+            }
+            // edges in both directions between two packages are drawn side by side
+            if edges.contains(&(to, from)) {
+                let offset = if reversed {
+                    MUTUAL_OFFSET
+                } else {
+                    -MUTUAL_OFFSET
+                };
+                for point in points.iter_mut() {
+                    point.0 += offset;
+                }
+            }
+            layout.edges.push(EdgeLayout {
+                from: names[from].to_string(),
+                to: names[to].to_string(),
+                points,
+            });
+        }
+
+        for node in self_loops {
+            let (x, y) = positions[node];
+            let right = x + nodes[node].width / 2.0;
+            layout.edges.push(EdgeLayout {
+                from: names[node].to_string(),
+                to: names[node].to_string(),
+                points: vec![
+                    (right, y - NODE_HEIGHT / 4.0),
+                    (right, y + NODE_HEIGHT / 4.0),
+                ],
+            });
+        }
+
+        layout
+    }
+}
+
+/// Returns for every edge, whether it closes a cycle found by a depth-first search and has to be reversed.
+fn find_back_edges(node_count: usize, edges: &[(usize, usize)]) -> Vec<bool> {
+    let mut successors: Vec<Vec<(usize, usize)>> = vec![Vec::new(); node_count];
+    for (e, &(from, to)) in edges.iter().enumerate() {
+        successors[from].push((to, e));
+    }
+
+    // 0 = unvisited, 1 = on the stack, 2 = finished
+    let mut state = vec![0u8; node_count];
+    let mut reversed = vec![false; edges.len()];
+    for start in 0..node_count {
+        if state[start] != 0 {
+            continue;
+        }
+        let mut stack = vec![(start, 0)];
+        state[start] = 1;
+        while let Some((node, next)) = stack.pop() {
+            if let Some(&(to, e)) = successors[node].get(next) {
+                stack.push((node, next + 1));
+                match state[to] {
+                    0 => {
+                        state[to] = 1;
+                        stack.push((to, 0));
+                    }
+                    1 => reversed[e] = true,
+                    _ => {}
+                }
+            } else {
+                state[node] = 2;
+            }
+        }
+    }
+    reversed
+}
+
+/// Assigns every node to the layer of the longest path reaching it, so every edge points to a lower layer.
+fn assign_layers(node_count: usize, edges: &[(usize, usize)]) -> Vec<usize> {
+    let mut in_degree = vec![0; node_count];
+    for &(_, to) in edges {
+        in_degree[to] += 1;
+    }
+    let mut queue: Vec<usize> = (0..node_count).filter(|&n| in_degree[n] == 0).collect();
+    let mut layers = vec![0; node_count];
+    while let Some(node) = queue.pop() {
+        for &(from, to) in edges {
+            if from == node {
+                layers[to] = layers[to].max(layers[node] + 1);
+                in_degree[to] -= 1;
+                if in_degree[to] == 0 {
+                    queue.push(to);
+                }
+            }
+        }
+    }
+    layers
+}
+
+/// Reorders the nodes within the layers by the barycenter of their neighbors, sweeping down and up alternately.
+/// The ordering with the fewest crossings is kept.
+fn order_layers(nodes: &[LayerNode], mut layers: Vec<Vec<usize>>) -> Vec<Vec<usize>> {
+    let mut best = layers.clone();
+    let mut best_crossings = count_crossings(nodes, &layers);
+
+    for sweep in 0..SWEEPS {
+        let downwards = sweep % 2 == 0;
+        for step in 1..layers.len() {
+            let (l, fixed) = if downwards {
+                (step, step - 1)
+            } else {
+                (layers.len() - 1 - step, layers.len() - step)
+            };
+            let position: HashMap<usize, usize> = layers[fixed]
+                .iter()
+                .enumerate()
+                .map(|(i, &n)| (n, i))
+                .collect();
+            let current: Vec<(usize, f64)> = layers[l]
+                .iter()
+                .enumerate()
+                .map(|(i, &n)| {
+                    let neighbors = if downwards {
+                        &nodes[n].predecessors
+                    } else {
+                        &nodes[n].successors
+                    };
+                    let fixed_positions: Vec<f64> = neighbors
+                        .iter()
+                        .filter_map(|neighbor| position.get(neighbor))
+                        .map(|&p| p as f64)
+                        .collect();
+                    // nodes without neighbors in the fixed layer keep their position
+                    let barycenter = if fixed_positions.is_empty() {
+                        i as f64
+                    } else {
+                        fixed_positions.iter().sum::<f64>() / fixed_positions.len() as f64
+                    };
+                    (n, barycenter)
+                })
+                .collect();
+            let mut sorted = current;
+            sorted.sort_by(|a, b| a.1.total_cmp(&b.1));
+            layers[l] = sorted.into_iter().map(|(n, _)| n).collect();
+        }
+
+        let crossings = count_crossings(nodes, &layers);
+        if crossings < best_crossings {
+            best_crossings = crossings;
+            best = layers.clone();
+        }
+    }
+    best
+}
+
+/// Counts the crossings of the edges between all adjacent layers.
+fn count_crossings(nodes: &[LayerNode], layers: &[Vec<usize>]) -> usize {
+    let mut crossings = 0;
+    for pair in layers.windows(2) {
+        let position: HashMap<usize, usize> =
+            pair[1].iter().enumerate().map(|(i, &n)| (n, i)).collect();
+        let edges: Vec<(usize, usize)> = pair[0]
+            .iter()
+            .enumerate()
+            .flat_map(|(i, &n)| {
+                nodes[n]
+                    .successors
+                    .iter()
+                    .filter_map(|s| position.get(s))
+                    .map(move |&p| (i, p))
+            })
+            .collect();
+        for (i, a) in edges.iter().enumerate() {
+            for b in &edges[i + 1..] {
+                if (a.0 < b.0 && a.1 > b.1) || (a.0 > b.0 && a.1 < b.1) {
+                    crossings += 1;
+                }
+            }
+        }
+    }
+    crossings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn find<'a>(layout: &'a Layout, name: &str) -> &'a NodeLayout {
+        layout.nodes.iter().find(|n| n.name == name).unwrap()
+    }
+
+    #[test]
+    fn test_dependencies_are_placed_below() {
+        let mut g = Graph::new();
+        g.add_edge("app", "core");
+        g.add_edge("app", "util");
+        g.add_edge("core", "util");
+        g.adjacency_list.insert("util".to_string(), vec![]);
+
+        let layout = g.layout();
+        assert!(find(&layout, "app").y < find(&layout, "core").y);
+        assert!(find(&layout, "core").y < find(&layout, "util").y);
+        // the edge spanning two layers passes through a dummy node
+        let edge = layout
+            .edges
+            .iter()
+            .find(|e| e.from == "app" && e.to == "util")
+            .unwrap();
+        assert_eq!(edge.points.len(), 3);
+    }
+
+    #[test]
+    fn test_cycle_is_laid_out() {
+        let mut g = Graph::new();
+        g.add_edge("a", "b");
+        g.add_edge("b", "a");
+        g.add_edge("c", "c");
+
+        let layout = g.layout();
+        assert_eq!(layout.nodes.len(), 3);
+        assert_eq!(layout.edges.len(), 3);
+        // the reversed edge still points from the dependent to the dependency
+        let edge = layout
+            .edges
+            .iter()
+            .find(|e| e.from == "b" && e.to == "a")
+            .unwrap();
+        assert!(edge.points[0].1 > edge.points[1].1);
+    }
+
+    #[test]
+    fn test_barycenter_removes_crossings() {
+        let nodes = vec![
+            LayerNode {
+                package: Some(0),
+                width: 10.0,
+                predecessors: vec![],
+                successors: vec![3],
+            },
+            LayerNode {
+                package: Some(1),
+                width: 10.0,
+                predecessors: vec![],
+                successors: vec![2],
+            },
+            LayerNode {
+                package: Some(2),
+                width: 10.0,
+                predecessors: vec![1],
+                successors: vec![],
+            },
+            LayerNode {
+                package: Some(3),
+                width: 10.0,
+                predecessors: vec![0],
+                successors: vec![],
+            },
+        ];
+        let layers = vec![vec![0, 1], vec![2, 3]];
+        assert_eq!(count_crossings(&nodes, &layers), 1);
+        let ordered = order_layers(&nodes, layers);
+        assert_eq!(count_crossings(&nodes, &ordered), 0);
+    }
+}
@@ -0,0 +1,223 @@
+use crate::cycles::CycleAnalysis;
+use crate::diagram_creation::edge_labels;
+use crate::graph::{DependencyKind, Graph};
+
+mod layout;
+
+const STYLE: &str = "\
+.node rect { fill: #ececff; stroke: #9370db; stroke-width: 1px; }
+.node.external-path rect { fill: #f5f5f5; stroke: #999; stroke-dasharray: 4 3; }
+.node text { font: 14px sans-serif; fill: #333; text-anchor: middle; dominant-baseline: central; }
+.edge { fill: none; stroke: #333; stroke-width: 1.5px; }
+.edge.dashed { stroke-dasharray: 4 3; }
+.edge.thick { stroke-width: 3px; }
+.edge.red { stroke: #ff0000; stroke-width: 2px; }
+.label { font: 11px sans-serif; fill: #555; text-anchor: middle; }
+";
+
+/// Renders the graph as a SVG image, without any external tools.
+/// Edges are styled like in the Mermaid diagram, and edges which are part of a circular dependency are drawn in red.
+/// External path packages are drawn with a dashed, grey box. The nodes of a condensed graph show the packages they
+/// consist of as tooltip.
+pub fn generate_svg(graph: &Graph, cycles: &CycleAnalysis) -> String {
+    let layout = graph.layout();
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">\n",
+        w = layout.width,
+        h = layout.height
+    );
+    svg.push_str("<defs>\n");
+    svg.push_str(&arrow_marker("arrow", "#333"));
+    svg.push_str(&arrow_marker("arrow-red", "#ff0000"));
+    svg.push_str("</defs>\n");
+    svg.push_str(&format!("<style>\n{}</style>\n", STYLE));
+
+    for edge in &layout.edges {
+        let in_cycle = cycles.contains_edge(&edge.from, &edge.to);
+        let mut classes = vec!["edge"];
+        if graph.is_edge_optional(&edge.from, &edge.to) {
+            classes.push("dashed");
+        } else {
+            match graph.get_edge_kind(&edge.from, &edge.to) {
+                DependencyKind::Normal => {}
+                DependencyKind::Build => classes.push("thick"),
+                DependencyKind::Dev => classes.push("dashed"),
+            }
+        }
+        if in_cycle {
+            classes.push("red");
+        }
+        let marker = if in_cycle { "arrow-red" } else { "arrow" };
+        svg.push_str(&format!(
+            "<path class=\"{}\" data-from=\"{}\" data-to=\"{}\" d=\"{}\" marker-end=\"url(#{})\"/>\n",
+            classes.join(" "),
+            escape_xml(&edge.from),
+            escape_xml(&edge.to),
+            path_data(&edge.points, edge.from == edge.to),
+            marker
+        ));
+
+        let labels = edge_labels(graph, &edge.from, &edge.to);
+        if !labels.is_empty() {
+            let (x, y) = label_position(&edge.points, edge.from == edge.to);
+            svg.push_str(&format!(
+                "<text class=\"label\" x=\"{}\" y=\"{}\">{}</text>\n",
+                x,
+                y,
+                escape_xml(&labels.join(", "))
+            ));
+        }
+    }
+
+    for node in &layout.nodes {
+        let class = if graph.is_external_path(&node.name) {
+            "node external-path"
+        } else {
+            "node"
+        };
+        let component = cycles
+            .component_of(&node.name)
+            .map(|i| format!(" data-component=\"{}\"", i + 1))
+            .unwrap_or_default();
+        let title = graph
+            .get_group_members(&node.name)
+            .map(|members| format!("<title>{}</title>", escape_xml(&members.join(", "))))
+            .unwrap_or_default();
+        svg.push_str(&format!(
+            "<g class=\"{}\" data-name=\"{}\"{}>{}<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" rx=\"4\"/><text x=\"{}\" y=\"{}\">{}</text></g>\n",
+            class,
+            escape_xml(&node.name),
+            component,
+            title,
+            node.x - node.width / 2.0,
+            node.y - node.height / 2.0,
+            node.width,
+            node.height,
+            node.x,
+            node.y,
+            escape_xml(&node.name)
+        ));
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+fn arrow_marker(id: &str, color: &str) -> String {
+    format!(
+        "<marker id=\"{}\" viewBox=\"0 0 10 10\" refX=\"10\" refY=\"5\" markerWidth=\"8\" markerHeight=\"8\" orient=\"auto\"><path d=\"M 0 0 L 10 5 L 0 10 z\" fill=\"{}\"/></marker>\n",
+        id, color
+    )
+}
+
+/// Returns the path of an edge. Self-loops are drawn as a curve on the right side of the node.
+fn path_data(points: &[(f64, f64)], self_loop: bool) -> String {
+    if self_loop {
+        let ((x1, y1), (x2, y2)) = (points[0], points[1]);
+        return format!(
+            "M {} {} C {} {} {} {} {} {}",
+            x1,
+            y1,
+            x1 + 30.0,
+            y1 - 15.0,
+            x2 + 30.0,
+            y2 + 15.0,
+            x2,
+            y2
+        );
+    }
+    points
+        .iter()
+        .enumerate()
+        .map(|(i, (x, y))| format!("{} {} {}", if i == 0 { "M" } else { "L" }, x, y))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Returns the position of an edge label, which is the middle of the edge.
+fn label_position(points: &[(f64, f64)], self_loop: bool) -> (f64, f64) {
+    if self_loop {
+        return (points[0].0 + 30.0, (points[0].1 + points[1].1) / 2.0);
+    }
+    let middle = points.len() / 2;
+    let (a, b) = (points[middle - 1], points[middle]);
+    ((a.0 + b.0) / 2.0, (a.1 + b.1) / 2.0)
+}
+
+/// Escapes text to be placed within XML or HTML.
+pub fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cycles::DEFAULT_CYCLE_LIMIT;
+    use crate::graph::EdgeInfo;
+
+    #[test]
+    fn test_nodes_and_edges() {
+        let mut g = Graph::new();
+        g.add_edge_with_info(
+            "app",
+            "core",
+            EdgeInfo {
+                kind: DependencyKind::Dev,
+                ..Default::default()
+            },
+        );
+        g.adjacency_list.insert("core".to_string(), vec![]);
+        g.adjacency_list.insert("standalone".to_string(), vec![]);
+
+        let svg = generate_svg(&g, &g.analyze_cycles(DEFAULT_CYCLE_LIMIT));
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(svg.contains(">app</text>"));
+        assert!(svg.contains("<g class=\"node\" data-name=\"app\">"));
+        assert!(svg.contains("data-from=\"app\" data-to=\"core\""));
+        assert!(svg.contains(">standalone</text>"));
+        assert!(svg.contains("<path class=\"edge dashed\""));
+        assert!(svg.contains("<text class=\"label\""));
+        assert!(svg.contains(">dev</text>"));
+        assert!(!svg.contains("marker-end=\"url(#arrow-red)\""));
+    }
+
+    #[test]
+    fn test_cycle_edges_are_red() {
+        let mut g = Graph::new();
+        g.add_edge("a", "b");
+        g.add_edge("b", "a");
+        g.add_edge("b", "c");
+        g.adjacency_list.insert("c".to_string(), vec![]);
+
+        let svg = generate_svg(&g, &g.analyze_cycles(DEFAULT_CYCLE_LIMIT));
+        assert_eq!(svg.matches("class=\"edge red\"").count(), 2);
+        assert_eq!(svg.matches("class=\"edge\" ").count(), 1);
+        assert!(svg.contains("<g class=\"node\" data-name=\"a\" data-component=\"1\">"));
+        assert!(svg.contains("<g class=\"node\" data-name=\"c\">"));
+    }
+
+    #[test]
+    fn test_escape_xml() {
+        assert_eq!(
+            escape_xml("cfg(a = \"b\") & <c>"),
+            "cfg(a = &quot;b&quot;) &amp; &lt;c&gt;"
+        );
+    }
+
+    #[test]
+    fn test_condensed_graph() {
+        let mut g = Graph::new();
+        g.add_edge("a", "b");
+        g.add_edge("b", "a");
+        let g = g.condense(&g.analyze_cycles(DEFAULT_CYCLE_LIMIT));
+
+        let svg = generate_svg(&g, &g.analyze_cycles(DEFAULT_CYCLE_LIMIT));
+        assert!(
+            svg.contains("<g class=\"node\" data-name=\"Component 1\"><title>a, b</title><rect")
+        );
+    }
+}
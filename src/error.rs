@@ -0,0 +1,215 @@
+use serde::Serialize;
+use std::fmt::{Display, Formatter};
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// The exit code for invalid command line arguments.
+pub const EXIT_USAGE: u8 = 2;
+/// The exit code if the workspace cannot be read, e.g. due to a missing or invalid manifest.
+pub const EXIT_INVALID_WORKSPACE: u8 = 3;
+/// The exit code if the analysis succeeded, but the workspace violates a policy which fails the run.
+pub const EXIT_POLICY_VIOLATION: u8 = 4;
+/// The exit code if the diagram cannot be rendered or written.
+pub const EXIT_RENDER_FAILURE: u8 = 5;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// The errors which abort the analysis.
+#[derive(Debug)]
+pub enum Error {
+    /// An option has an invalid value.
+    Usage(String),
+    /// There is no `Cargo.toml` in the given directory.
+    MissingManifest(PathBuf),
+    /// The root manifest has neither a `[workspace]` nor a `[package]` table.
+    NotAWorkspace(PathBuf),
+    /// A file could not be read.
+    Read { path: PathBuf, source: io::Error },
+    /// A manifest or lockfile is not valid TOML or has an unexpected structure.
+    Toml {
+        path: PathBuf,
+        content: String,
+        source: Box<toml::de::Error>,
+    },
+    /// The output of `cargo metadata` could not be parsed.
+    Metadata {
+        source: String,
+        error: serde_json::Error,
+    },
+    /// A pattern of `workspace.members` is not a valid glob.
+    InvalidGlob {
+        pattern: String,
+        source: glob::PatternError,
+    },
+    /// The Mermaid CLI is required, but not installed.
+    MmdcNotInstalled,
+    /// Rendering the diagram with an external tool failed.
+    Render(String),
+    /// The diagram could not be written.
+    Write { path: PathBuf, source: io::Error },
+    /// The JSON report could not be serialized.
+    Serialize(serde_json::Error),
+    /// The workspace violates a policy which fails the run.
+    PolicyViolation(String),
+}
+
+/// A problem which did not abort the analysis, e.g. a manifest which could not be parsed and was skipped.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Diagnostic {
+    pub path: PathBuf,
+    pub reason: String,
+}
+
+impl Diagnostic {
+    pub fn new(path: &Path, error: &Error) -> Self {
+        Diagnostic {
+            path: path.to_path_buf(),
+            reason: error.reason(),
+        }
+    }
+}
+
+impl Error {
+    /// Returns the documented exit code of the error.
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            Error::Usage(_) => EXIT_USAGE,
+            Error::MissingManifest(_)
+            | Error::NotAWorkspace(_)
+            | Error::Read { .. }
+            | Error::Toml { .. }
+            | Error::Metadata { .. }
+            | Error::InvalidGlob { .. } => EXIT_INVALID_WORKSPACE,
+            Error::PolicyViolation(_) => EXIT_POLICY_VIOLATION,
+            Error::MmdcNotInstalled
+            | Error::Render(_)
+            | Error::Write { .. }
+            | Error::Serialize(_) => EXIT_RENDER_FAILURE,
+        }
+    }
+
+    /// Returns the description of the error without the path of the offending file.
+    pub fn reason(&self) -> String {
+        match self {
+            Error::Toml {
+                content, source, ..
+            } => {
+                let message = source.message().trim().replace('\n', ", ");
+                match source.span() {
+                    Some(span) => {
+                        let (line, column) = line_and_column(content, span.start);
+                        format!("line {}, column {}: {}", line, column, message)
+                    }
+                    None => message,
+                }
+            }
+            Error::Read { source, .. } => source.to_string(),
+            error => error.to_string(),
+        }
+    }
+
+    pub fn toml(path: &Path, content: &str, source: toml::de::Error) -> Self {
+        Error::Toml {
+            path: path.to_path_buf(),
+            content: content.to_string(),
+            source: Box::new(source),
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Usage(message) => write!(f, "{}", message),
+            Error::MissingManifest(dir) => {
+                write!(f, "no Cargo.toml found in {}", dir.display())
+            }
+            Error::NotAWorkspace(path) => write!(
+                f,
+                "{} doesn't seem to be a workspace or package, there is neither a [workspace] nor a [package] table",
+                path.display()
+            ),
+            Error::Read { path, source } => {
+                write!(f, "failed to read {}: {}", path.display(), source)
+            }
+            Error::Toml {
+                path,
+                content,
+                source,
+            } => {
+                // point to the offending location, the message of the TOML parser contains the snippet
+                match source.span() {
+                    Some(span) => {
+                        let (line, column) = line_and_column(content, span.start);
+                        write!(f, "failed to parse {}:{}:{}", path.display(), line, column)?;
+                    }
+                    None => write!(f, "failed to parse {}", path.display())?,
+                }
+                write!(f, "\n{}", source.to_string().trim_end())
+            }
+            Error::Metadata { source, error } => {
+                write!(f, "failed to parse the metadata of {}: {}", source, error)
+            }
+            Error::InvalidGlob { pattern, source } => {
+                write!(f, "invalid member pattern `{}`: {}", pattern, source)
+            }
+            Error::MmdcNotInstalled => write!(
+                f,
+                "Mermaid CLI (`mmdc`) is not installed. \
+                 Please install it by running `npm install -g @mermaid-js/mermaid-cli`."
+            ),
+            Error::Render(message) => write!(f, "failed to render the diagram: {}", message),
+            Error::Write { path, source } => {
+                write!(f, "failed to write {}: {}", path.display(), source)
+            }
+            Error::Serialize(error) => write!(f, "failed to serialize the report: {}", error),
+            Error::PolicyViolation(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Returns the 1-based line and column of a byte offset.
+fn line_and_column(content: &str, offset: usize) -> (usize, usize) {
+    let before = &content[..offset.min(content.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before.chars().rev().take_while(|c| *c != '\n').count() + 1;
+    (line, column)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toml_error_points_to_span() {
+        let content = "[package]\nname = \"a\"\nversion = {{version}}\n";
+        let source = toml::from_str::<toml::Value>(content).unwrap_err();
+        let error = Error::toml(Path::new("a/Cargo.toml"), content, source);
+
+        let message = error.to_string();
+        assert!(message.starts_with("failed to parse a/Cargo.toml:3:12"));
+        assert!(message.contains("{{version}}"));
+        assert_eq!(error.exit_code(), EXIT_INVALID_WORKSPACE);
+
+        let diagnostic = Diagnostic::new(Path::new("a/Cargo.toml"), &error);
+        assert_eq!(
+            diagnostic.reason,
+            "line 3, column 12: invalid inline table, expected `}`"
+        );
+    }
+
+    #[test]
+    fn test_exit_codes() {
+        assert_eq!(
+            Error::NotAWorkspace(PathBuf::from("Cargo.toml")).exit_code(),
+            EXIT_INVALID_WORKSPACE
+        );
+        assert_eq!(Error::MmdcNotInstalled.exit_code(), EXIT_RENDER_FAILURE);
+        assert_eq!(
+            Error::PolicyViolation(String::new()).exit_code(),
+            EXIT_POLICY_VIOLATION
+        );
+    }
+}
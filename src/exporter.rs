@@ -1,75 +1,117 @@
 use crate::arguments::FileExportOptions;
-use std::fs::{remove_file, File};
+use crate::error::{Error, Result};
+use std::fs::{read_to_string, File};
 use std::io::Write;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
 const SVG_OUTPUT: &str = "workspace-analyzer.svg";
 const MMD_OUTPUT: &str = "workspace-analyzer.mmd";
+const DOT_OUTPUT: &str = "workspace-analyzer.dot";
+const HTML_OUTPUT: &str = "workspace-analyzer.html";
 
-pub fn export(content: &str, output_format: FileExportOptions) {
-    // let path = Path::new(MMD_OUTPUT_PATH);
-    write_mermaid_code_to_file(content, MMD_OUTPUT);
+/// Where the diagram is written to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Destination {
+    File(PathBuf),
+    Stdout,
+}
 
-    match output_format {
-        FileExportOptions::SVG => {
-            replace_mmd_file_with_svg();
-            println!("✅ File successfully written to: {}", SVG_OUTPUT);
-        }
-        FileExportOptions::MMD => {
-            println!("✅ File successfully written to: {}", MMD_OUTPUT);
+impl Destination {
+    /// Returns the destination given by `--output`, where `-` stands for stdout.
+    /// Defaults to a file named after the output format in the current working directory.
+    pub fn new(output: Option<&str>, output_format: FileExportOptions) -> Self {
+        match output {
+            Some("-") => Destination::Stdout,
+            Some(path) => Destination::File(PathBuf::from(path)),
+            None => Destination::File(PathBuf::from(match output_format {
+                FileExportOptions::SVG => SVG_OUTPUT,
+                FileExportOptions::MMD => MMD_OUTPUT,
+                FileExportOptions::DOT => DOT_OUTPUT,
+                FileExportOptions::HTML => HTML_OUTPUT,
+            })),
         }
     }
 }
 
-pub fn replace_mmd_file_with_svg() {
-    verify_mmdc_installation();
-    render_mermaid_to_svg();
-    if let Err(e) = remove_file(MMD_OUTPUT) {
-        println!("Failed to remove file: {}", e);
+/// Writes the diagram to the destination. The content is expected to be in the export format,
+/// except for SVGs rendered with `mmdc`, which expect Mermaid code.
+pub fn export(
+    content: &str,
+    output_format: FileExportOptions,
+    use_mmdc: bool,
+    destination: &Destination,
+) -> Result<()> {
+    let content = if output_format == FileExportOptions::SVG && use_mmdc {
+        render_mermaid_to_svg(content)?
+    } else {
+        content.to_string()
+    };
+
+    match destination {
+        Destination::Stdout => {
+            print!("{}", content);
+        }
+        Destination::File(path) => {
+            write_to_file(&content, path)?;
+            eprintln!("✅ File successfully written to: {}", path.display());
+        }
     }
+    Ok(())
 }
 
-fn verify_mmdc_installation() {
+fn verify_mmdc_installation() -> Result<()> {
     let is_installed = Command::new("mmdc")
         .arg("--version")
         .output()
         .map(|output| output.status.success())
         .unwrap_or(false);
 
-    if !is_installed {
-        eprintln!(
-            "Error: Mermaid CLI (`mmdc`) is not installed. \
-             Please install it by running `npm install -g @mermaid-js/mermaid-cli`."
-        );
-
-        panic!("Mermaid CLI (`mmdc`) not found");
+    if is_installed {
+        Ok(())
+    } else {
+        Err(Error::MmdcNotInstalled)
     }
 }
 
-fn write_mermaid_code_to_file(mermaid_code: &str, file_path: &str) {
-    let mut file = File::create(file_path).expect("Unable to create file.");
-    file.write_all(mermaid_code.as_bytes())
-        .expect("Unable to write to file.");
+fn write_to_file(content: &str, file_path: impl AsRef<Path>) -> Result<()> {
+    let file_path = file_path.as_ref();
+    File::create(file_path)
+        .and_then(|mut file| file.write_all(content.as_bytes()))
+        .map_err(|source| Error::Write {
+            path: file_path.to_path_buf(),
+            source,
+        })
 }
 
-/// Renders the `.mmd` file as a SVG using `mmdc`.
-fn render_mermaid_to_svg() {
-    let output = Command::new("mmdc")
+/// Renders the Mermaid code as a SVG using `mmdc`.
+/// The intermediate files are placed in a temporary directory, which is removed afterwards.
+fn render_mermaid_to_svg(mermaid_code: &str) -> Result<String> {
+    verify_mmdc_installation()?;
+
+    let temp_dir = tempfile::tempdir().map_err(|e| Error::Render(e.to_string()))?;
+    let input = temp_dir.path().join(MMD_OUTPUT);
+    let output = temp_dir.path().join(SVG_OUTPUT);
+    write_to_file(mermaid_code, &input)?;
+
+    let result = Command::new("mmdc")
         .arg("-i")
-        .arg(MMD_OUTPUT)
+        .arg(&input)
         .arg("-o")
-        .arg(SVG_OUTPUT)
+        .arg(&output)
         .output()
-        .unwrap_or_else(|e| {
-            panic!("Failed to execute mmdc: {}", e);
-        });
-
-    if !output.status.success() {
-        eprintln!(
-            "Error rendering diagram: {}",
-            String::from_utf8_lossy(&output.stderr)
-        );
+        .map_err(|e| Error::Render(format!("failed to execute mmdc: {}", e)))?;
+
+    if !result.status.success() {
+        return Err(Error::Render(
+            String::from_utf8_lossy(&result.stderr).to_string(),
+        ));
     }
+
+    read_to_string(&output).map_err(|source| Error::Read {
+        path: output,
+        source,
+    })
 }
 
 #[cfg(test)]
@@ -79,10 +121,10 @@ mod tests {
     use std::fs;
 
     #[test]
-    fn test_write_mermaid_code_to_file() {
+    fn test_write_to_file() {
         let temp_file = assert_fs::NamedTempFile::new("test.mmd").unwrap();
         let test_content = "graph TD; A-->B;";
-        write_mermaid_code_to_file(test_content, temp_file.path().to_str().unwrap());
+        write_to_file(test_content, temp_file.path().to_str().unwrap()).unwrap();
         assert!(temp_file.path().exists(), "Expected file was not created.");
         assert_eq!(fs::read_to_string(temp_file.path()).unwrap(), test_content);
     }
@@ -93,7 +135,29 @@ mod tests {
         let mmd_output_path = temp_dir.child("workspace-analyzer.mmd");
         let test_content = "graph TD; A-->B;";
         std::env::set_current_dir(&temp_dir).expect("Failed to change directory");
-        export(test_content, FileExportOptions::MMD);
+        export(
+            test_content,
+            FileExportOptions::MMD,
+            false,
+            &Destination::new(None, FileExportOptions::MMD),
+        )
+        .unwrap();
         assert!(mmd_output_path.path().exists(), "MMD file was not created.");
     }
+
+    #[test]
+    fn test_destination() {
+        assert_eq!(
+            Destination::new(None, FileExportOptions::DOT),
+            Destination::File(PathBuf::from("workspace-analyzer.dot"))
+        );
+        assert_eq!(
+            Destination::new(Some("out/graph.svg"), FileExportOptions::SVG),
+            Destination::File(PathBuf::from("out/graph.svg"))
+        );
+        assert_eq!(
+            Destination::new(Some("-"), FileExportOptions::SVG),
+            Destination::Stdout
+        );
+    }
 }
@@ -0,0 +1,377 @@
+use crate::graph::{DependencyKind, Graph};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// The features to evaluate the graph for, similar to the feature flags of `cargo build`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct FeatureSelection {
+    /// Features given as `feature` apply to every package defining it, `package/feature` to a single package.
+    pub features: Vec<String>,
+    pub all_features: bool,
+    pub no_default_features: bool,
+    /// The packages to enable all features of.
+    pub all_features_for: Vec<String>,
+    /// The packages to evaluate without their default features.
+    pub no_default_features_for: Vec<String>,
+}
+
+impl FeatureSelection {
+    fn has_all_features(&self, package: &str) -> bool {
+        self.all_features || self.all_features_for.iter().any(|p| p == package)
+    }
+
+    fn has_default_features(&self, package: &str) -> bool {
+        !self.no_default_features && !self.no_default_features_for.iter().any(|p| p == package)
+    }
+
+    /// Returns the explicitly selected features of a package.
+    fn requested_for(&self, package: &str) -> Vec<String> {
+        self.features
+            .iter()
+            .filter_map(|feature| match feature.split_once('/') {
+                Some((pkg, feature)) if pkg == package => Some(feature.to_string()),
+                Some(_) => None,
+                None => Some(feature.clone()),
+            })
+            .collect()
+    }
+}
+
+/// The result of resolving the features of all packages.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ResolvedFeatures {
+    /// The enabled features of each package.
+    pub enabled: HashMap<String, HashSet<String>>,
+    /// The activated optional dependencies, as pairs of the package and the dependency key.
+    pub activated: HashSet<(String, String)>,
+}
+
+impl Graph {
+    /// Resolves which features are enabled and which optional dependencies are activated for the selection.
+    /// Features are unified across the workspace, i.e. features a package enables on its dependencies are taken
+    /// into account as well as their default features. Like Cargo's resolver 2, features enabled by dev-dependencies
+    /// are not unified into the normal build.
+    pub fn resolve_features(&self, selection: &FeatureSelection) -> ResolvedFeatures {
+        let mut resolved = ResolvedFeatures::default();
+        let mut queue: VecDeque<(String, String)> = VecDeque::new();
+        // features requested via `dep?/feature`, which only apply once the dependency is activated
+        let mut weak: HashMap<(String, String), Vec<String>> = HashMap::new();
+
+        for package in self.adjacency_list.keys() {
+            if selection.has_all_features(package) {
+                for feature in self
+                    .features
+                    .get(package)
+                    .into_iter()
+                    .flat_map(|f| f.keys())
+                {
+                    queue.push_back((package.clone(), feature.clone()));
+                }
+                for (key, _) in self.optional_dependencies(package) {
+                    queue.push_back((package.clone(), format!("dep:{}", key)));
+                }
+            }
+            if selection.has_default_features(package) {
+                queue.push_back((package.clone(), "default".to_string()));
+            }
+            for feature in selection.requested_for(package) {
+                queue.push_back((package.clone(), feature));
+            }
+
+            // Unconditional dependencies always enable their features, except dev-dependencies
+            for dep in &self.adjacency_list[package] {
+                for info in self.get_edge_infos(package, dep) {
+                    if !info.optional && info.kind != DependencyKind::Dev {
+                        enable_on_dependency(
+                            &mut queue,
+                            dep,
+                            &info.features,
+                            info.no_default_features,
+                        );
+                    }
+                }
+            }
+        }
+
+        while let Some((package, feature)) = queue.pop_front() {
+            let enabled = resolved.enabled.entry(package.clone()).or_default();
+            if !enabled.insert(feature.clone()) {
+                continue;
+            }
+
+            if let Some(key) = feature.strip_prefix("dep:") {
+                if resolved
+                    .activated
+                    .insert((package.clone(), key.to_string()))
+                {
+                    for (dep_key, dep) in self.optional_dependencies(&package) {
+                        if dep_key == key {
+                            for info in self.get_edge_infos(&package, dep) {
+                                enable_on_dependency(
+                                    &mut queue,
+                                    dep,
+                                    &info.features,
+                                    info.no_default_features,
+                                );
+                            }
+                        }
+                    }
+                    for dep_feature in weak
+                        .remove(&(package.clone(), key.to_string()))
+                        .unwrap_or_default()
+                    {
+                        self.enable_dependency_feature(&mut queue, &package, key, &dep_feature);
+                    }
+                }
+            } else if let Some((key, dep_feature)) = feature.split_once('/') {
+                match key.strip_suffix('?') {
+                    Some(key)
+                        if self.is_optional_dependency(&package, key)
+                            && !resolved
+                                .activated
+                                .contains(&(package.clone(), key.to_string())) =>
+                    {
+                        weak.entry((package.clone(), key.to_string()))
+                            .or_default()
+                            .push(dep_feature.to_string());
+                    }
+                    Some(key) => {
+                        self.enable_dependency_feature(&mut queue, &package, key, dep_feature);
+                    }
+                    None => {
+                        if self.is_optional_dependency(&package, key) {
+                            queue.push_back((package.clone(), format!("dep:{}", key)));
+                        }
+                        self.enable_dependency_feature(&mut queue, &package, key, dep_feature);
+                    }
+                }
+            } else if let Some(values) = self.features.get(&package).and_then(|f| f.get(&feature)) {
+                for value in values {
+                    queue.push_back((package.clone(), value.clone()));
+                }
+            } else if self.is_optional_dependency(&package, &feature) {
+                // the implicit feature of an optional dependency
+                queue.push_back((package.clone(), format!("dep:{}", feature)));
+            }
+        }
+
+        resolved
+    }
+
+    /// Keeps only the optional dependencies which are activated by the selected features.
+    pub fn filter_by_features(&self, selection: &FeatureSelection) -> Graph {
+        let resolved = self.resolve_features(selection);
+        self.filter_edge_infos(|from, to, info| {
+            !info.optional
+                || resolved
+                    .activated
+                    .contains(&(from.to_string(), info.key(to).to_string()))
+        })
+    }
+
+    /// Returns the keys and target packages of the optional dependencies of a package.
+    fn optional_dependencies<'a>(&'a self, package: &str) -> Vec<(&'a str, &'a str)> {
+        self.adjacency_list
+            .get(package)
+            .into_iter()
+            .flatten()
+            .flat_map(|dep| {
+                self.get_edge_infos(package, dep)
+                    .iter()
+                    .filter(|info| info.optional)
+                    .map(move |info| (info.key(dep), dep.as_str()))
+            })
+            .collect()
+    }
+
+    fn is_optional_dependency(&self, package: &str, key: &str) -> bool {
+        self.optional_dependencies(package)
+            .iter()
+            .any(|(dep_key, _)| *dep_key == key)
+    }
+
+    /// Enables a feature on the dependency which is declared with `key` by `package`.
+    fn enable_dependency_feature(
+        &self,
+        queue: &mut VecDeque<(String, String)>,
+        package: &str,
+        key: &str,
+        feature: &str,
+    ) {
+        for dep in self.adjacency_list.get(package).into_iter().flatten() {
+            if self
+                .get_edge_infos(package, dep)
+                .iter()
+                .any(|info| info.key(dep) == key)
+            {
+                queue.push_back((dep.clone(), feature.to_string()));
+            }
+        }
+    }
+}
+
+fn enable_on_dependency(
+    queue: &mut VecDeque<(String, String)>,
+    dep: &str,
+    features: &[String],
+    no_default_features: bool,
+) {
+    if !no_default_features {
+        queue.push_back((dep.to_string(), "default".to_string()));
+    }
+    for feature in features {
+        queue.push_back((dep.to_string(), feature.clone()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::EdgeInfo;
+
+    /// Creates a graph where `app` optionally depends on `cli` and `tls`, and `cli` optionally on `color`.
+    fn setup_graph() -> Graph {
+        let mut graph = Graph::new();
+        graph.add_edge("app", "core");
+        graph.add_edge_with_info(
+            "app",
+            "cli",
+            EdgeInfo {
+                optional: true,
+                ..Default::default()
+            },
+        );
+        graph.add_edge_with_info(
+            "app",
+            "tls",
+            EdgeInfo {
+                optional: true,
+                ..Default::default()
+            },
+        );
+        graph.add_edge_with_info(
+            "cli",
+            "color",
+            EdgeInfo {
+                optional: true,
+                ..Default::default()
+            },
+        );
+        graph.adjacency_list.insert("core".to_string(), vec![]);
+        graph.adjacency_list.insert("tls".to_string(), vec![]);
+        graph.adjacency_list.insert("color".to_string(), vec![]);
+        graph.features.insert(
+            "app".to_string(),
+            HashMap::from([
+                ("default".to_string(), vec!["dep:cli".to_string()]),
+                ("secure".to_string(), vec!["dep:tls".to_string()]),
+                ("fancy".to_string(), vec!["cli?/color".to_string()]),
+            ]),
+        );
+        graph
+    }
+
+    #[test]
+    fn test_default_features() {
+        let graph = setup_graph().filter_by_features(&FeatureSelection::default());
+        assert_eq!(graph.adjacency_list["app"], vec!["core", "cli"]);
+        assert!(graph.adjacency_list["cli"].is_empty());
+    }
+
+    #[test]
+    fn test_no_default_features() {
+        let selection = FeatureSelection {
+            no_default_features: true,
+            ..Default::default()
+        };
+        let graph = setup_graph().filter_by_features(&selection);
+        assert_eq!(graph.adjacency_list["app"], vec!["core"]);
+    }
+
+    #[test]
+    fn test_selected_features() {
+        let selection = FeatureSelection {
+            features: vec!["secure".to_string(), "app/fancy".to_string()],
+            ..Default::default()
+        };
+        let graph = setup_graph().filter_by_features(&selection);
+        assert_eq!(graph.adjacency_list["app"], vec!["core", "cli", "tls"]);
+        // `fancy` enables the implicit feature `color` of `cli`, since `cli` is activated
+        assert_eq!(graph.adjacency_list["cli"], vec!["color"]);
+    }
+
+    #[test]
+    fn test_weak_feature_without_dependency() {
+        let selection = FeatureSelection {
+            features: vec!["fancy".to_string()],
+            no_default_features: true,
+            ..Default::default()
+        };
+        let graph = setup_graph().filter_by_features(&selection);
+        assert_eq!(graph.adjacency_list["app"], vec!["core"]);
+        assert!(graph.adjacency_list["cli"].is_empty());
+    }
+
+    #[test]
+    fn test_all_features() {
+        let selection = FeatureSelection {
+            all_features: true,
+            no_default_features: true,
+            ..Default::default()
+        };
+        let graph = setup_graph().filter_by_features(&selection);
+        assert_eq!(graph.adjacency_list["app"], vec!["core", "cli", "tls"]);
+        assert_eq!(graph.adjacency_list["cli"], vec!["color"]);
+    }
+
+    #[test]
+    fn test_features_per_package() {
+        let selection = FeatureSelection {
+            all_features_for: vec!["cli".to_string()],
+            no_default_features_for: vec!["app".to_string()],
+            ..Default::default()
+        };
+        let graph = setup_graph().filter_by_features(&selection);
+        // `cli` is not activated by `app` without its default features, but its own features are all enabled
+        assert_eq!(graph.adjacency_list["app"], vec!["core"]);
+        assert_eq!(graph.adjacency_list["cli"], vec!["color"]);
+    }
+
+    #[test]
+    fn test_dev_dependency_features_are_not_unified() {
+        let mut graph = setup_graph();
+        graph.add_edge_with_info(
+            "bench",
+            "app",
+            EdgeInfo {
+                kind: DependencyKind::Dev,
+                features: vec!["secure".to_string()],
+                ..Default::default()
+            },
+        );
+        let graph = graph.filter_by_features(&FeatureSelection::default());
+        assert_eq!(graph.adjacency_list["app"], vec!["core", "cli"]);
+
+        let mut graph = setup_graph();
+        graph.add_edge_with_info(
+            "bench",
+            "app",
+            EdgeInfo {
+                features: vec!["secure".to_string()],
+                ..Default::default()
+            },
+        );
+        let graph = graph.filter_by_features(&FeatureSelection::default());
+        assert_eq!(graph.adjacency_list["app"], vec!["core", "cli", "tls"]);
+    }
+
+    #[test]
+    fn test_enabling_features() {
+        let graph = setup_graph();
+        assert_eq!(graph.get_enabling_features("app", "cli"), vec!["default"]);
+        assert_eq!(graph.get_enabling_features("app", "tls"), vec!["secure"]);
+        // without `dep:` syntax, the optional dependency is a feature itself
+        assert_eq!(graph.get_enabling_features("cli", "color"), vec!["color"]);
+        assert!(!graph.is_edge_optional("app", "core"));
+        assert!(graph.is_edge_optional("app", "tls"));
+    }
+}
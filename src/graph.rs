@@ -1,26 +1,231 @@
-use std::collections::HashMap;
+use crate::types::nested::Features;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::str::FromStr;
 
 /// Represents a directed graph.
 #[derive(Debug, PartialEq, Eq)]
 pub struct Graph {
     pub(crate) adjacency_list: HashMap<String, Vec<String>>,
+    /// Details about the edges, keyed by `(from, to)`.
+    /// Edges without an entry are treated as plain normal dependencies.
+    pub(crate) edge_infos: HashMap<(String, String), Vec<EdgeInfo>>,
+    /// The `[features]` table of each package.
+    pub(crate) features: HashMap<String, Features>,
+    /// The packages which are reached via path dependencies, but aren't members of the workspace.
+    pub(crate) external_paths: HashSet<String>,
+    /// The packages which are collapsed into each node of a condensed graph.
+    pub(crate) groups: BTreeMap<String, Vec<String>>,
 }
 
+/// The dependency table an edge was declared in.
+/// The order goes from the strongest to the weakest kind.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DependencyKind {
+    #[default]
+    Normal,
+    Build,
+    Dev,
+}
+
+impl DependencyKind {
+    pub const ALL: [DependencyKind; 3] = [
+        DependencyKind::Normal,
+        DependencyKind::Build,
+        DependencyKind::Dev,
+    ];
+}
+
+impl FromStr for DependencyKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "normal" => Ok(DependencyKind::Normal),
+            "build" => Ok(DependencyKind::Build),
+            "dev" => Ok(DependencyKind::Dev),
+            _ => Err(format!(
+                "Invalid dependency kind: {}. Allowed: normal, build, dev",
+                s
+            )),
+        }
+    }
+}
+
+/// Details about a single declared dependency.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct EdgeInfo {
+    pub kind: DependencyKind,
+    /// The name under which the dependency is used, if it is renamed via the `package` key.
+    pub alias: Option<String>,
+    /// The platform the dependency is restricted to, e.g. `cfg(unix)`.
+    pub target: Option<String>,
+    /// Whether the dependency is only used when a feature enables it.
+    pub optional: bool,
+    /// The features which are enabled on the dependency.
+    pub features: Vec<String>,
+    /// Whether the default features of the dependency are disabled.
+    pub no_default_features: bool,
+    /// The version requirement, e.g. `1.0.200`.
+    pub version: Option<String>,
+    /// Whether the dependency is inherited from `[workspace.dependencies]`.
+    pub inherited: bool,
+}
+
+impl EdgeInfo {
+    /// Returns the name under which the dependency is declared, i.e. its key in the dependency table.
+    pub fn key<'a>(&'a self, to: &'a str) -> &'a str {
+        self.alias.as_deref().unwrap_or(to)
+    }
+}
+
+/// Used for edges which were added without any further details.
+const PLAIN_EDGE: &[EdgeInfo] = &[EdgeInfo {
+    kind: DependencyKind::Normal,
+    alias: None,
+    target: None,
+    optional: false,
+    features: Vec::new(),
+    no_default_features: false,
+    version: None,
+    inherited: false,
+}];
+
 impl Graph {
     pub(crate) fn new() -> Self {
         Graph {
             adjacency_list: HashMap::new(),
+            edge_infos: HashMap::new(),
+            features: HashMap::new(),
+            external_paths: HashSet::new(),
+            groups: BTreeMap::new(),
         }
     }
 
     /// Adds a directed edge from `from` to `to`.
+    #[cfg(test)]
     pub(crate) fn add_edge(&mut self, from: &str, to: &str) {
         self.adjacency_list
             .entry(from.to_string())
-            .or_insert_with(Vec::new)
+            .or_default()
             .push(to.to_string());
     }
 
+    /// Adds a directed edge from `from` to `to` together with its details.
+    /// If the edge already exists, only the details are added to it.
+    pub(crate) fn add_edge_with_info(&mut self, from: &str, to: &str, info: EdgeInfo) {
+        let dependencies = self.adjacency_list.entry(from.to_string()).or_default();
+        if !dependencies.iter().any(|dep| dep == to) {
+            dependencies.push(to.to_string());
+        }
+        self.edge_infos
+            .entry((from.to_string(), to.to_string()))
+            .or_default()
+            .push(info);
+    }
+
+    /// Adds the packages of another graph, which are expected to be distinct from the packages of this graph.
+    pub(crate) fn extend(&mut self, other: Graph) {
+        self.adjacency_list.extend(other.adjacency_list);
+        self.edge_infos.extend(other.edge_infos);
+        self.features.extend(other.features);
+        self.external_paths.extend(other.external_paths);
+        self.groups.extend(other.groups);
+    }
+
+    /// Returns the details of the edge from `from` to `to`.
+    pub fn get_edge_infos(&self, from: &str, to: &str) -> &[EdgeInfo] {
+        self.edge_infos
+            .get(&(from.to_string(), to.to_string()))
+            .map(|infos| infos.as_slice())
+            .unwrap_or(PLAIN_EDGE)
+    }
+
+    /// Returns the strongest kind with which `from` depends on `to`.
+    pub fn get_edge_kind(&self, from: &str, to: &str) -> DependencyKind {
+        self.get_edge_infos(from, to)
+            .iter()
+            .map(|info| info.kind)
+            .min()
+            .unwrap_or_default()
+    }
+
+    /// Returns the distinct aliases under which `from` uses `to`.
+    pub fn get_edge_aliases(&self, from: &str, to: &str) -> Vec<&str> {
+        let mut aliases: Vec<&str> = self
+            .get_edge_infos(from, to)
+            .iter()
+            .filter_map(|info| info.alias.as_deref())
+            .collect();
+        aliases.sort();
+        aliases.dedup();
+        aliases
+    }
+
+    /// Returns the distinct platforms `from` depends on `to` for.
+    /// The list is empty if the dependency applies to every platform.
+    pub fn get_edge_targets(&self, from: &str, to: &str) -> Vec<&str> {
+        let infos = self.get_edge_infos(from, to);
+        if infos.iter().any(|info| info.target.is_none()) {
+            return Vec::new();
+        }
+        let mut targets: Vec<&str> = infos
+            .iter()
+            .filter_map(|info| info.target.as_deref())
+            .collect();
+        targets.sort();
+        targets.dedup();
+        targets
+    }
+
+    /// Whether `from` only depends on `to` if a feature enables the dependency.
+    pub fn is_edge_optional(&self, from: &str, to: &str) -> bool {
+        self.get_edge_infos(from, to)
+            .iter()
+            .all(|info| info.optional)
+    }
+
+    /// Returns the features of `from` which directly enable its optional dependency on `to`.
+    pub fn get_enabling_features(&self, from: &str, to: &str) -> Vec<String> {
+        let features = self.features.get(from);
+        let mut enabling = Vec::new();
+
+        for info in self.get_edge_infos(from, to) {
+            if !info.optional {
+                continue;
+            }
+            let key = info.key(to);
+            let explicit = format!("dep:{}", key);
+            let has_explicit = features
+                .is_some_and(|features| features.values().flatten().any(|v| *v == explicit));
+
+            if let Some(features) = features {
+                for (feature, values) in features {
+                    let enables = values.iter().any(|value| {
+                        *value == explicit
+                            || (!has_explicit && value == key)
+                            || value
+                                .strip_prefix(key)
+                                .is_some_and(|rest| rest.starts_with('/'))
+                    });
+                    if enables {
+                        enabling.push(feature.clone());
+                    }
+                }
+            }
+
+            // Without `dep:` syntax, an optional dependency also acts as a feature of the same name
+            if !has_explicit && features.is_none_or(|features| !features.contains_key(key)) {
+                enabling.push(key.to_string());
+            }
+        }
+
+        enabling.sort();
+        enabling.dedup();
+        enabling
+    }
+
     pub fn get_edge_count(&self) -> usize {
         self.adjacency_list.values().map(|deps| deps.len()).sum()
     }
@@ -28,6 +233,27 @@ impl Graph {
     pub fn get_node_count(&self) -> usize {
         self.adjacency_list.len()
     }
+
+    /// Whether the package is reached via a path dependency, but isn't a member of the workspace.
+    pub fn is_external_path(&self, package: &str) -> bool {
+        self.external_paths.contains(package)
+    }
+
+    /// Returns the packages which are collapsed into the node, if the graph is condensed.
+    pub fn get_group_members(&self, node: &str) -> Option<&[String]> {
+        self.groups.get(node).map(|members| members.as_slice())
+    }
+
+    /// Returns the graph of either the workspace members or the external path packages,
+    /// with the edges which start at them.
+    pub fn split_external_paths(&self, external: bool) -> Graph {
+        let mut graph =
+            self.filter_edge_infos(|from, _, _| self.is_external_path(from) == external);
+        graph
+            .adjacency_list
+            .retain(|package, _| self.is_external_path(package) == external);
+        graph
+    }
 }
 
 #[cfg(test)]
@@ -63,4 +289,34 @@ mod tests {
         // Verify that "E" has no outgoing edges
         assert!(adj_list.get("E").is_none());
     }
+
+    #[test]
+    fn test_add_edge_with_info() {
+        let mut graph = Graph::new();
+        graph.add_edge_with_info(
+            "A",
+            "B",
+            EdgeInfo {
+                kind: DependencyKind::Dev,
+                ..Default::default()
+            },
+        );
+        graph.add_edge_with_info(
+            "A",
+            "B",
+            EdgeInfo {
+                kind: DependencyKind::Build,
+                ..Default::default()
+            },
+        );
+        graph.add_edge("A", "C");
+
+        // The edge is only added once, but carries both kinds
+        assert_eq!(graph.adjacency_list["A"], vec!["B", "C"]);
+        assert_eq!(graph.get_edge_infos("A", "B").len(), 2);
+        assert_eq!(graph.get_edge_kind("A", "B"), DependencyKind::Build);
+
+        // Edges without details are normal dependencies
+        assert_eq!(graph.get_edge_kind("A", "C"), DependencyKind::Normal);
+    }
 }
@@ -0,0 +1,247 @@
+use crate::cycles::cycle_text;
+use crate::diagram_creation::escape_xml;
+use crate::report::Report;
+
+const STYLE: &str = "\
+body { font-family: sans-serif; margin: 2em; color: #333; }
+h1, h2 { font-weight: normal; }
+table { border-collapse: collapse; margin-bottom: 2em; }
+th, td { border: 1px solid #ccc; padding: 4px 12px; text-align: left; }
+th.sortable { cursor: pointer; user-select: none; background: #f4f4f4; }
+th.asc::after { content: ' ▲'; }
+th.desc::after { content: ' ▼'; }
+#graph { overflow: auto; border: 1px solid #ccc; margin-bottom: 2em; }
+#graph .node { cursor: pointer; }
+#graph .node.selected rect { fill: #ffd27f; }
+#graph .node.dependency rect { fill: #b9e4b0; }
+#graph .node.dependent rect { fill: #a9cbf5; }
+#graph .faded { opacity: 0.2; }
+.legend span { display: inline-block; padding: 2px 8px; margin-right: 8px; }
+";
+
+const SCRIPT: &str = r#"
+document.querySelectorAll('th.sortable').forEach(function (th) {
+  th.addEventListener('click', function () {
+    var table = th.closest('table');
+    var index = Array.prototype.indexOf.call(th.parentNode.children, th);
+    var ascending = !th.classList.contains('asc');
+    table.querySelectorAll('th').forEach(function (h) { h.classList.remove('asc', 'desc'); });
+    th.classList.add(ascending ? 'asc' : 'desc');
+    var body = table.tBodies[0];
+    var rows = Array.prototype.slice.call(body.rows);
+    rows.sort(function (a, b) {
+      var x = a.cells[index].textContent, y = b.cells[index].textContent;
+      var result = isNaN(x) || isNaN(y) ? x.localeCompare(y) : x - y;
+      return ascending ? result : -result;
+    });
+    rows.forEach(function (row) { body.appendChild(row); });
+  });
+});
+
+var selected = null;
+document.querySelectorAll('#graph .node').forEach(function (node) {
+  node.addEventListener('click', function () {
+    var name = node.dataset.name;
+    selected = selected === name ? null : name;
+    var dependencies = {}, dependents = {};
+    document.querySelectorAll('#graph .edge').forEach(function (edge) {
+      var related = edge.dataset.from === selected || edge.dataset.to === selected;
+      edge.classList.toggle('faded', selected !== null && !related);
+      if (edge.dataset.from === selected) dependencies[edge.dataset.to] = true;
+      if (edge.dataset.to === selected) dependents[edge.dataset.from] = true;
+    });
+    document.querySelectorAll('#graph .node').forEach(function (other) {
+      var n = other.dataset.name;
+      other.classList.toggle('selected', n === selected);
+      other.classList.toggle('dependency', n !== selected && dependencies[n] === true);
+      other.classList.toggle('dependent', n !== selected && dependents[n] === true);
+      other.classList.toggle('faded', selected !== null && n !== selected && !dependencies[n] && !dependents[n]);
+    });
+  });
+});
+"#;
+
+/// Generates a self-contained HTML page with the diagram, the counts, the coupling metrics, the layers and the circular
+/// dependencies. Clicking a package in the diagram highlights its dependencies and dependents.
+pub fn generate_html(report: &Report, svg: &str) -> String {
+    let mut html =
+        String::from("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    html.push_str("<title>Cargo Workspace Analyzer</title>\n");
+    html.push_str(&format!("<style>\n{}</style>\n</head>\n<body>\n", STYLE));
+    html.push_str("<h1>Cargo Workspace Analyzer</h1>\n");
+
+    html.push_str("<h2>Workspace</h2>\n");
+    html.push_str(
+        "<p class=\"legend\">Click a package to highlight it: \
+        <span style=\"background: #b9e4b0\">dependency</span>\
+        <span style=\"background: #a9cbf5\">dependent</span></p>\n",
+    );
+    html.push_str(&format!("<div id=\"graph\">\n{}</div>\n", svg));
+
+    html.push_str("<h2>Counts</h2>\n");
+    let mut counts = vec![
+        vec![
+            "Total".to_string(),
+            report.counts.total.packages.to_string(),
+            report.counts.total.dependencies.to_string(),
+        ],
+        vec![
+            "Workspace".to_string(),
+            report.counts.workspace.packages.to_string(),
+            report.counts.workspace.dependencies.to_string(),
+        ],
+    ];
+    if report.counts.external_path.packages > 0 {
+        counts.push(vec![
+            "External Path".to_string(),
+            report.counts.external_path.packages.to_string(),
+            report.counts.external_path.dependencies.to_string(),
+        ]);
+    }
+    html.push_str(&table(
+        &["Category", "Packages", "Dependencies"],
+        counts,
+        false,
+    ));
+
+    html.push_str("<h2>Coupling</h2>\n");
+    html.push_str(&table(
+        &["Package", "Level", "FanIn", "FanOut", "Instability"],
+        report
+            .coupling
+            .iter()
+            .map(|entry| {
+                vec![
+                    entry.package.clone(),
+                    entry.level.to_string(),
+                    entry.fan_in.to_string(),
+                    entry.fan_out.to_string(),
+                    format!("{:.2}", entry.instability),
+                ]
+            })
+            .collect(),
+        true,
+    ));
+
+    html.push_str("<h2>Layers</h2>\n");
+    html.push_str(&table(
+        &["Level", "Packages"],
+        report
+            .layers
+            .iter()
+            .enumerate()
+            .map(|(level, packages)| vec![level.to_string(), packages.join(", ")])
+            .collect(),
+        false,
+    ));
+
+    html.push_str("<h2>Circular Dependencies</h2>\n");
+    if report.cycles.components.is_empty() {
+        html.push_str("<p>No circular dependencies found.</p>\n");
+    } else {
+        html.push_str(&table(
+            &["Component", "Packages"],
+            report
+                .cycles
+                .components
+                .iter()
+                .enumerate()
+                .map(|(i, component)| vec![(i + 1).to_string(), component.join(", ")])
+                .collect(),
+            false,
+        ));
+        html.push_str("<ul>\n");
+        for path in &report.cycles.paths {
+            html.push_str(&format!("<li>{}</li>\n", escape_xml(&cycle_text(path))));
+        }
+        html.push_str("</ul>\n");
+        if report.cycles.truncated {
+            html.push_str(&format!(
+                "<p>Only the first {} cycles are listed.</p>\n",
+                report.cycles.paths.len()
+            ));
+        }
+        html.push_str("<p>Removing these dependencies makes the workspace acyclic:</p>\n");
+        html.push_str(&table(
+            &["Dependency", "Component", "Cycles"],
+            report
+                .cycles
+                .feedback_arc_set
+                .iter()
+                .map(|edge| {
+                    vec![
+                        format!("{} → {}", edge.from, edge.to),
+                        (edge.component + 1).to_string(),
+                        edge.cycles.to_string(),
+                    ]
+                })
+                .collect(),
+            false,
+        ));
+    }
+
+    html.push_str(&format!("<script>{}</script>\n</body>\n</html>\n", SCRIPT));
+    html
+}
+
+fn table(headers: &[&str], rows: Vec<Vec<String>>, sortable: bool) -> String {
+    let class = if sortable { " class=\"sortable\"" } else { "" };
+    let mut html = String::from("<table>\n<thead><tr>");
+    for header in headers {
+        html.push_str(&format!("<th{}>{}</th>", class, header));
+    }
+    html.push_str("</tr></thead>\n<tbody>\n");
+    for row in rows {
+        html.push_str("<tr>");
+        for cell in row {
+            html.push_str(&format!("<td>{}</td>", escape_xml(&cell)));
+        }
+        html.push_str("</tr>\n");
+    }
+    html.push_str("</tbody>\n</table>\n");
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cycles::DEFAULT_CYCLE_LIMIT;
+    use crate::diagram_creation::generate_svg;
+    use crate::graph::Graph;
+
+    #[test]
+    fn test_html_report() {
+        let mut graph = Graph::new();
+        graph.add_edge("a", "b");
+        graph.add_edge("b", "a");
+        graph.add_edge("b", "<c>");
+        graph.adjacency_list.insert("<c>".to_string(), vec![]);
+        graph.add_edge("a", "shared");
+        graph.adjacency_list.insert("shared".to_string(), vec![]);
+        graph.external_paths.insert("shared".to_string());
+        let cycles = graph.analyze_cycles(DEFAULT_CYCLE_LIMIT);
+        let report = Report::new(
+            &graph,
+            &graph,
+            graph.calculate_coupling(),
+            &graph.calculate_levels(&cycles),
+            &cycles,
+        );
+
+        let html = generate_html(
+            &report,
+            &generate_svg(&graph, &graph.analyze_cycles(DEFAULT_CYCLE_LIMIT)),
+        );
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        // everything is inlined, nothing is loaded from elsewhere
+        assert!(!html.contains("src=\""));
+        assert!(!html.contains("href=\""));
+        assert!(html.contains("<div id=\"graph\">\n<svg"));
+        assert!(html.contains("<th class=\"sortable\">FanIn</th>"));
+        assert!(html.contains("<td>&lt;c&gt;</td><td>0</td>"));
+        assert!(html.contains("<tr><td>External Path</td><td>1</td><td>0</td></tr>"));
+        assert!(html.contains("<tr><td>0</td><td>&lt;c&gt;, shared</td></tr>"));
+        assert!(html.contains("<li>a → b → a</li>"));
+        assert!(html.contains("<td>a → b</td><td>1</td><td>1</td>"));
+    }
+}
@@ -0,0 +1,128 @@
+use crate::cycles::CycleAnalysis;
+use crate::graph::Graph;
+use std::collections::HashMap;
+
+type PackageName = String;
+
+pub type Levels = HashMap<PackageName, usize>;
+
+impl Graph {
+    /// Assigns each package its level, which is the length of the longest path to a package without dependencies, so
+    /// every package is placed above its dependencies. The packages of a circular dependency share a level, hence the
+    /// analysis of the cycles of this graph is required.
+    pub fn calculate_levels(&self, cycles: &CycleAnalysis) -> Levels {
+        let condensed = self.condense(cycles);
+
+        let mut node_levels: HashMap<&str, usize> = HashMap::new();
+        for node in condensed.adjacency_list.keys() {
+            level_of(&condensed, node, &mut node_levels);
+        }
+
+        let mut levels = Levels::new();
+        for (node, level) in node_levels {
+            match condensed.get_group_members(node) {
+                Some(members) => {
+                    for package in members {
+                        levels.insert(package.clone(), level);
+                    }
+                }
+                None => {
+                    levels.insert(node.to_string(), level);
+                }
+            }
+        }
+        levels
+    }
+}
+
+/// Returns the level of a node of an acyclic graph, memorizing the levels of all nodes visited on the way.
+fn level_of<'a>(graph: &'a Graph, node: &'a str, levels: &mut HashMap<&'a str, usize>) -> usize {
+    if let Some(&level) = levels.get(node) {
+        return level;
+    }
+
+    let level = graph.adjacency_list[node]
+        .iter()
+        .filter(|dep| graph.adjacency_list.contains_key(*dep))
+        .map(|dep| level_of(graph, dep, levels) + 1)
+        .max()
+        .unwrap_or(0);
+    levels.insert(node, level);
+    level
+}
+
+/// Groups the packages by their level, starting with the packages without dependencies.
+/// The packages of each layer are sorted alphabetically.
+pub fn layers(levels: &Levels) -> Vec<Vec<String>> {
+    let mut layers: Vec<Vec<String>> = Vec::new();
+    for (package, &level) in levels {
+        if layers.len() <= level {
+            layers.resize(level + 1, Vec::new());
+        }
+        layers[level].push(package.clone());
+    }
+    for layer in &mut layers {
+        layer.sort();
+    }
+    layers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cycles::DEFAULT_CYCLE_LIMIT;
+
+    fn levels(edges: &[(&str, &str)]) -> Levels {
+        let mut graph = Graph::new();
+        for (from, to) in edges {
+            graph.add_edge(from, to);
+            graph.adjacency_list.entry(to.to_string()).or_default();
+        }
+        graph.calculate_levels(&graph.analyze_cycles(DEFAULT_CYCLE_LIMIT))
+    }
+
+    #[test]
+    fn test_longest_path() {
+        // `app` depends on `core` directly and via `service`, so it is placed above `service`
+        let levels = levels(&[
+            ("app", "core"),
+            ("app", "service"),
+            ("service", "core"),
+            ("cli", "core"),
+            ("core", "core"),
+        ]);
+
+        assert_eq!(levels["core"], 0);
+        assert_eq!(levels["service"], 1);
+        assert_eq!(levels["cli"], 1);
+        assert_eq!(levels["app"], 2);
+        assert_eq!(
+            layers(&levels),
+            vec![
+                vec!["core".to_string()],
+                vec!["cli".to_string(), "service".to_string()],
+                vec!["app".to_string()]
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cycles_share_a_level() {
+        let levels = levels(&[("app", "a"), ("a", "b"), ("b", "a"), ("b", "core")]);
+
+        assert_eq!(levels["core"], 0);
+        assert_eq!(levels["a"], 1);
+        assert_eq!(levels["b"], 1);
+        assert_eq!(levels["app"], 2);
+    }
+
+    #[test]
+    fn test_ignore_edges_to_other_packages() {
+        let mut graph = Graph::new();
+        graph.add_edge("app", "serde");
+        graph.adjacency_list.remove("serde");
+
+        let levels = graph.calculate_levels(&graph.analyze_cycles(DEFAULT_CYCLE_LIMIT));
+        assert_eq!(levels, Levels::from([("app".to_string(), 0)]));
+    }
+}
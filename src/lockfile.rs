@@ -0,0 +1,523 @@
+use crate::error::Result;
+use crate::manifests_collector::reader::load_cargo_toml_content;
+use crate::types::lockfile::Lockfile;
+use serde::Serialize;
+use std::cmp::Ordering;
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::path::Path;
+
+/// A package which is part of the resolved graph.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct LockedPackage {
+    pub name: String,
+    pub version: String,
+    pub source: Option<String>,
+}
+
+impl LockedPackage {
+    /// Returns a short description of where the package comes from.
+    pub fn origin(&self) -> &str {
+        match self.source.as_deref() {
+            None => "path",
+            Some(source) if source.contains("crates.io") => "crates.io",
+            Some(source) if source.starts_with("git+") => "git",
+            Some(source) if source.starts_with("registry+") || source.starts_with("sparse+") => {
+                "registry"
+            }
+            Some(source) => source,
+        }
+    }
+}
+
+/// The fully resolved dependency graph of `Cargo.lock`, including all third-party packages.
+#[derive(Debug, Default)]
+pub struct LockGraph {
+    pub packages: Vec<LockedPackage>,
+    /// The dependencies of each package, given as indices into `packages`.
+    pub dependencies: Vec<Vec<usize>>,
+}
+
+/// How much a workspace member depends on external packages.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ExternalWeight {
+    pub direct: usize,
+    /// The amount of distinct external packages which are pulled in, directly or transitively.
+    pub transitive: usize,
+    /// The longest chain of external packages below the member.
+    pub depth: usize,
+}
+
+pub type ExternalWeights = HashMap<String, ExternalWeight>;
+
+/// An external crate which is resolved in more than one version.
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub struct DuplicateCrate {
+    pub name: String,
+    pub versions: Vec<DuplicateVersion>,
+}
+
+/// One of the versions of a duplicated crate, together with the members pulling it in.
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub struct DuplicateVersion {
+    pub version: String,
+    /// The shortest dependency chain from each responsible member to this version, starting with the member.
+    pub chains: Vec<Vec<String>>,
+}
+
+/// Reads and resolves the lockfile at the given path.
+/// Returns `None` if it does not exist.
+pub fn load_lock_graph(path: &Path) -> Result<Option<LockGraph>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let lockfile = load_cargo_toml_content::<Lockfile>(path)?;
+    Ok(Some(LockGraph::new(&lockfile)))
+}
+
+impl LockGraph {
+    pub fn new(lockfile: &Lockfile) -> Self {
+        let packages: Vec<LockedPackage> = lockfile
+            .packages
+            .iter()
+            .map(|package| LockedPackage {
+                name: package.name.clone(),
+                version: package.version.clone(),
+                source: package.source.clone(),
+            })
+            .collect();
+
+        let dependencies = lockfile
+            .packages
+            .iter()
+            .map(|package| {
+                package
+                    .dependencies
+                    .iter()
+                    .filter_map(|dep| find_package(&packages, dep))
+                    .collect()
+            })
+            .collect();
+
+        LockGraph {
+            packages,
+            dependencies,
+        }
+    }
+
+    /// Returns the indices of the workspace members, i.e. the packages without a source and one of the given names.
+    pub fn member_indices(&self, members: &HashSet<&str>) -> Vec<usize> {
+        (0..self.packages.len())
+            .filter(|&i| {
+                self.packages[i].source.is_none()
+                    && members.contains(self.packages[i].name.as_str())
+            })
+            .collect()
+    }
+
+    /// Returns all packages reachable from `start` without passing a workspace member,
+    /// together with their distance from `start`.
+    pub fn reachable_externals(&self, start: usize, members: &[usize]) -> HashMap<usize, usize> {
+        let mut distances = HashMap::new();
+        let mut queue = VecDeque::from([(start, 0)]);
+        let mut visited = HashSet::from([start]);
+
+        while let Some((current, distance)) = queue.pop_front() {
+            for &dep in &self.dependencies[current] {
+                if members.contains(&dep) || !visited.insert(dep) {
+                    continue;
+                }
+                distances.insert(dep, distance + 1);
+                queue.push_back((dep, distance + 1));
+            }
+        }
+
+        distances
+    }
+
+    /// Calculates for each selected member how many external packages it pulls in.
+    /// The walk stops at all workspace members, so members outside the selection are not counted as external.
+    pub fn calculate_external_weights(
+        &self,
+        members: &HashSet<&str>,
+        selected: &HashSet<&str>,
+    ) -> ExternalWeights {
+        let member_indices = self.member_indices(members);
+
+        self.member_indices(selected)
+            .iter()
+            .map(|&member| {
+                let reachable = self.reachable_externals(member, &member_indices);
+                let weight = ExternalWeight {
+                    direct: reachable.values().filter(|&&d| d == 1).count(),
+                    transitive: reachable.len(),
+                    depth: self.longest_external_chain(member, &member_indices),
+                };
+                (self.packages[member].name.clone(), weight)
+            })
+            .collect()
+    }
+
+    /// Returns the direct external dependencies of the selected members together with the members using them.
+    pub fn direct_externals(
+        &self,
+        members: &HashSet<&str>,
+        selected: &HashSet<&str>,
+    ) -> Vec<(&LockedPackage, BTreeSet<&str>)> {
+        let member_indices = self.member_indices(members);
+        let mut used_by: HashMap<usize, BTreeSet<&str>> = HashMap::new();
+
+        for member in self.member_indices(selected) {
+            for &dep in &self.dependencies[member] {
+                if !member_indices.contains(&dep) {
+                    used_by
+                        .entry(dep)
+                        .or_default()
+                        .insert(self.packages[member].name.as_str());
+                }
+            }
+        }
+
+        let mut externals: Vec<_> = used_by
+            .into_iter()
+            .map(|(dep, members)| (&self.packages[dep], members))
+            .collect();
+        externals.sort();
+        externals
+    }
+
+    /// Finds the external crates which are resolved in multiple versions
+    /// and attributes every version to the selected members pulling it in.
+    /// Crates which none of the selected members pull in are left out.
+    pub fn find_duplicates(
+        &self,
+        members: &HashSet<&str>,
+        selected: &HashSet<&str>,
+    ) -> Vec<DuplicateCrate> {
+        let member_indices = self.member_indices(members);
+        let selected_indices = self.member_indices(selected);
+        let mut by_name: HashMap<&str, Vec<usize>> = HashMap::new();
+        for (i, package) in self.packages.iter().enumerate() {
+            if !member_indices.contains(&i) {
+                by_name.entry(package.name.as_str()).or_default().push(i);
+            }
+        }
+
+        let mut duplicates: Vec<DuplicateCrate> = by_name
+            .into_iter()
+            .filter(|(_, versions)| versions.len() > 1)
+            .map(|(name, mut versions)| {
+                versions.sort_by(|a, b| {
+                    compare_versions(&self.packages[*a].version, &self.packages[*b].version)
+                });
+                let versions = versions
+                    .into_iter()
+                    .map(|version| DuplicateVersion {
+                        version: self.packages[version].version.clone(),
+                        chains: selected_indices
+                            .iter()
+                            .filter_map(|&member| {
+                                self.shortest_chain(member, version, &member_indices)
+                            })
+                            .map(|chain| self.describe_chain(&chain))
+                            .collect(),
+                    })
+                    .collect();
+                DuplicateCrate {
+                    name: name.to_string(),
+                    versions,
+                }
+            })
+            .filter(|duplicate: &DuplicateCrate| {
+                duplicate.versions.iter().any(|v| !v.chains.is_empty())
+            })
+            .collect();
+
+        duplicates.sort_by(|a, b| a.name.cmp(&b.name));
+        for duplicate in &mut duplicates {
+            for version in &mut duplicate.versions {
+                version.chains.sort();
+            }
+        }
+        duplicates
+    }
+
+    /// Returns the shortest chain of packages from `start` to `target`, not passing any other member.
+    fn shortest_chain(&self, start: usize, target: usize, members: &[usize]) -> Option<Vec<usize>> {
+        let mut parents: HashMap<usize, usize> = HashMap::new();
+        let mut queue = VecDeque::from([start]);
+
+        while let Some(current) = queue.pop_front() {
+            if current == target {
+                let mut chain = vec![target];
+                while let Some(&parent) = parents.get(chain.last()?) {
+                    chain.push(parent);
+                }
+                chain.reverse();
+                return Some(chain);
+            }
+            for &dep in &self.dependencies[current] {
+                if dep == start || members.contains(&dep) || parents.contains_key(&dep) {
+                    continue;
+                }
+                parents.insert(dep, current);
+                queue.push_back(dep);
+            }
+        }
+
+        None
+    }
+
+    fn describe_chain(&self, chain: &[usize]) -> Vec<String> {
+        chain
+            .iter()
+            .map(|&i| {
+                let package = &self.packages[i];
+                if package.source.is_none() {
+                    package.name.clone()
+                } else {
+                    format!("{} {}", package.name, package.version)
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the length of the longest path of external packages below `start`.
+    /// Cycles can only exist through dev-dependencies and are cut when a package is revisited.
+    fn longest_external_chain(&self, start: usize, members: &[usize]) -> usize {
+        fn visit(
+            graph: &LockGraph,
+            node: usize,
+            members: &[usize],
+            on_path: &mut HashSet<usize>,
+            memo: &mut HashMap<usize, usize>,
+        ) -> usize {
+            if let Some(&depth) = memo.get(&node) {
+                return depth;
+            }
+            on_path.insert(node);
+            let mut depth = 0;
+            for &dep in &graph.dependencies[node] {
+                if !members.contains(&dep) && !on_path.contains(&dep) {
+                    depth = depth.max(1 + visit(graph, dep, members, on_path, memo));
+                }
+            }
+            on_path.remove(&node);
+            memo.insert(node, depth);
+            depth
+        }
+
+        visit(
+            self,
+            start,
+            members,
+            &mut HashSet::new(),
+            &mut HashMap::new(),
+        )
+    }
+}
+
+/// Compares two versions by their numeric components, so that `1.10.0` is greater than `1.9.0`.
+fn compare_versions(a: &str, b: &str) -> Ordering {
+    let components = |version: &str| -> Vec<u64> {
+        version
+            .split(['.', '-', '+'])
+            .map(|part| part.parse().unwrap_or(0))
+            .collect()
+    };
+    components(a).cmp(&components(b)).then_with(|| a.cmp(b))
+}
+
+/// Finds the package a dependency entry of the lockfile refers to.
+fn find_package(packages: &[LockedPackage], entry: &str) -> Option<usize> {
+    let mut parts = entry.splitn(3, ' ');
+    let name = parts.next()?;
+    let version = parts.next();
+    let source = parts
+        .next()
+        .map(|source| source.trim_start_matches('(').trim_end_matches(')'));
+
+    packages.iter().position(|package| {
+        package.name == name
+            && version.is_none_or(|version| package.version == version)
+            && source.is_none_or(|source| package.source.as_deref() == Some(source))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CRATES_IO: &str = "registry+https://github.com/rust-lang/crates.io-index";
+
+    fn setup_lock_graph() -> LockGraph {
+        let lockfile: Lockfile = toml::from_str(&format!(
+            r#"
+            version = 4
+
+            [[package]]
+            name = "app"
+            version = "0.1.0"
+            dependencies = ["core", "serde", "syn 1.0.109"]
+
+            [[package]]
+            name = "core"
+            version = "0.1.0"
+            dependencies = ["serde"]
+
+            [[package]]
+            name = "serde"
+            version = "1.0.200"
+            source = "{0}"
+            dependencies = ["serde_derive"]
+
+            [[package]]
+            name = "serde_derive"
+            version = "1.0.200"
+            source = "{0}"
+            dependencies = ["syn 2.0.60 ({0})"]
+
+            [[package]]
+            name = "syn"
+            version = "1.0.109"
+            source = "{0}"
+
+            [[package]]
+            name = "syn"
+            version = "2.0.60"
+            source = "{0}"
+            "#,
+            CRATES_IO
+        ))
+        .unwrap();
+        LockGraph::new(&lockfile)
+    }
+
+    #[test]
+    fn test_resolve_dependency_entries() {
+        let graph = setup_lock_graph();
+        let names = |i: usize| -> Vec<String> {
+            graph.dependencies[i]
+                .iter()
+                .map(|&d| format!("{} {}", graph.packages[d].name, graph.packages[d].version))
+                .collect()
+        };
+
+        assert_eq!(names(0), vec!["core 0.1.0", "serde 1.0.200", "syn 1.0.109"]);
+        assert_eq!(names(3), vec!["syn 2.0.60"]);
+        assert_eq!(graph.packages[2].origin(), "crates.io");
+        assert_eq!(graph.packages[0].origin(), "path");
+    }
+
+    #[test]
+    fn test_external_weights() {
+        let graph = setup_lock_graph();
+        let members = HashSet::from(["app", "core"]);
+        let weights = graph.calculate_external_weights(&members, &members);
+
+        // `core` is a member, so it is neither counted nor followed
+        assert_eq!(
+            weights["app"],
+            ExternalWeight {
+                direct: 2,
+                transitive: 4,
+                depth: 3,
+            }
+        );
+        assert_eq!(
+            weights["core"],
+            ExternalWeight {
+                direct: 1,
+                transitive: 3,
+                depth: 3,
+            }
+        );
+    }
+
+    #[test]
+    fn test_direct_externals() {
+        let graph = setup_lock_graph();
+        let members = HashSet::from(["app", "core"]);
+        let externals = graph.direct_externals(&members, &members);
+
+        let described: Vec<(String, Vec<&str>)> = externals
+            .iter()
+            .map(|(package, members)| {
+                (
+                    format!("{} {}", package.name, package.version),
+                    members.iter().copied().collect(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            described,
+            vec![
+                ("serde 1.0.200".to_string(), vec!["app", "core"]),
+                ("syn 1.0.109".to_string(), vec!["app"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_compare_versions() {
+        assert_eq!(compare_versions("1.9.0", "1.10.0"), Ordering::Less);
+        assert_eq!(compare_versions("2.0.0", "1.99.99"), Ordering::Greater);
+        assert_eq!(compare_versions("1.0.0", "1.0.0"), Ordering::Equal);
+    }
+
+    #[test]
+    fn test_find_duplicates() {
+        let graph = setup_lock_graph();
+        let members = HashSet::from(["app", "core"]);
+        let duplicates = graph.find_duplicates(&members, &members);
+
+        assert_eq!(
+            duplicates,
+            vec![DuplicateCrate {
+                name: "syn".to_string(),
+                versions: vec![
+                    DuplicateVersion {
+                        version: "1.0.109".to_string(),
+                        chains: vec![vec!["app".to_string(), "syn 1.0.109".to_string()]],
+                    },
+                    DuplicateVersion {
+                        version: "2.0.60".to_string(),
+                        chains: vec![
+                            vec![
+                                "app".to_string(),
+                                "serde 1.0.200".to_string(),
+                                "serde_derive 1.0.200".to_string(),
+                                "syn 2.0.60".to_string(),
+                            ],
+                            vec![
+                                "core".to_string(),
+                                "serde 1.0.200".to_string(),
+                                "serde_derive 1.0.200".to_string(),
+                                "syn 2.0.60".to_string(),
+                            ],
+                        ],
+                    },
+                ],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_selected_members() {
+        let graph = setup_lock_graph();
+        let members = HashSet::from(["app", "core"]);
+        let selected = HashSet::from(["core"]);
+
+        // `app` is not selected, but still a member, so it is not reported as an external crate
+        let weights = graph.calculate_external_weights(&members, &selected);
+        assert_eq!(weights.keys().collect::<Vec<_>>(), vec!["core"]);
+        let externals = graph.direct_externals(&members, &selected);
+        assert_eq!(externals.len(), 1);
+        assert_eq!(externals[0].0.name, "serde");
+        assert_eq!(externals[0].1, BTreeSet::from(["core"]));
+
+        let duplicates = graph.find_duplicates(&members, &selected);
+        assert_eq!(duplicates.len(), 1);
+        assert!(duplicates[0].versions[0].chains.is_empty());
+        assert_eq!(duplicates[0].versions[1].chains.len(), 1);
+    }
+}
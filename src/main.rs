@@ -1,45 +1,216 @@
 #![warn(missing_docs)]
 #![doc = include_str!("../README.md")]
 
-use crate::arguments::get_args;
-use crate::diagram_creation::create_diagram;
-use crate::exporter::export;
+use crate::arguments::{get_args, Arguments, FileExportOptions, ReportFormat, Scope};
+use crate::cycles::CycleAnalysis;
+use crate::diagram_creation::{generate_dot, generate_mermaid_markdown, generate_svg};
+use crate::error::{Error, Result};
+use crate::exporter::{export, Destination};
+use crate::html_report::generate_html;
+use crate::lockfile::load_lock_graph;
 use crate::manifests_collector::get_dependency_graph;
-use crate::output::{print_counts, print_coupling};
-use crate::package_counter::count_packages;
+use crate::metadata_collector::get_dependency_graph_from_metadata;
+use crate::output::{
+    print_counts, print_coupling, print_cycles, print_duplicates, print_external_dependencies,
+    print_json_report, print_layers, print_skipped_manifests, print_version_drift,
+};
+use crate::platform::Platform;
+use crate::report::Report;
+use std::collections::HashSet;
 use std::path::Path;
+use std::process::ExitCode;
 
 mod arguments;
+mod condensation;
+mod cycles;
 mod dependency_filter;
 mod diagram_creation;
+mod error;
 mod exporter;
+mod features;
 mod graph;
+mod html_report;
+mod layers;
+mod lockfile;
 mod manifests_collector;
+mod metadata_collector;
 mod metrics;
 mod output;
-mod package_counter;
+mod platform;
+mod report;
 mod types;
+mod version_drift;
 
-fn main() {
+fn main() -> ExitCode {
     let args = get_args();
 
-    // count packages
-    let _amount_of_packages = count_packages(&args.directory);
+    match run(&args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("❌ Error: {}", error);
+            ExitCode::from(error.exit_code())
+        }
+    }
+}
+
+fn run(args: &Arguments) -> Result<()> {
+    // load filtered manifests, or the metadata provided by cargo
+    let (graph, default_members, diagnostics) = match &args.metadata {
+        Some(source) => {
+            let (graph, default_members) = get_dependency_graph_from_metadata(source)?;
+            (graph, default_members, Vec::new())
+        }
+        None => get_dependency_graph(Path::new(&args.directory), &args.exclude_dir)?,
+    };
+
+    // manifests which could not be read are skipped, unless the run is strict
+    if args.strict && !diagnostics.is_empty() {
+        let skipped: Vec<String> = diagnostics
+            .iter()
+            .map(|d| format!("{}: {}", d.path.display(), d.reason))
+            .collect();
+        return Err(Error::PolicyViolation(format!(
+            "{} manifest(s) were skipped:\n{}",
+            diagnostics.len(),
+            skipped.join("\n")
+        )));
+    }
+
+    // only keep the selected kinds of dependencies
+    let mut graph = graph.filter_by_kinds(&args.dependency_kinds().0);
+
+    // evaluate platform specific dependencies, if a platform is given
+    if args.target.is_some() || !args.cfg.is_empty() {
+        let mut platform = args
+            .target
+            .as_deref()
+            .map(Platform::from_triple)
+            .unwrap_or_default();
+        for cfg in &args.cfg {
+            platform.add_cfg(cfg);
+        }
+        graph = graph.filter_by_platform(&platform);
+    }
+
+    // only keep the optional dependencies which are activated by the selected features
+    if let Some(selection) = args.feature_selection() {
+        graph = graph.filter_by_features(&selection);
+    }
+
+    // all members bound the analysis of the lockfile, also if the scope only shows some of them
+    let workspace: Vec<String> = graph.adjacency_list.keys().cloned().collect();
 
-    // load filtered manifests
-    let graph = get_dependency_graph(Path::new(&args.directory));
+    // restrict the analysis to the default members, optionally with their dependencies
+    match args.scope {
+        Scope::All => {}
+        Scope::DefaultMembers => graph = graph.filter_by_packages(&default_members, false, false),
+        Scope::DefaultMembersWithDependencies => {
+            graph =
+                graph.filter_by_packages(&default_members, true, args.dev_dependencies_selected())
+        }
+    }
 
     // filter dependencies to only include references to workspace members
     let filtered = graph.filter_dependencies();
 
-    print_counts(&graph, &filtered);
+    // find the circular dependencies
+    let cycles = filtered.analyze_cycles(args.cycle_limit);
+
+    // collapse the circular dependencies, so the layering of the workspace becomes visible
+    let condensed = args.condense.then(|| filtered.condense(&cycles));
+    let shown = condensed.as_ref().unwrap_or(&filtered);
+    // the condensed graph is acyclic, so there is nothing to highlight in its diagram
+    let no_cycles = CycleAnalysis::default();
+    let highlighted = if args.condense { &no_cycles } else { &cycles };
+
+    // calculate the metrics
+    let metrics = shown.calculate_coupling();
+
+    // assign the packages to architectural layers
+    let levels = shown.calculate_levels(highlighted);
+
+    // find external dependencies which are declared inconsistently
+    let drift = args.drift.then(|| graph.find_version_drift());
+
+    // load the lockfile to analyze the external crates pulled in by the workspace members
+    let lock_graph = if args.external || args.duplicates {
+        let lockfile_path = args.lockfile_path();
+        let lock_graph = load_lock_graph(&lockfile_path)?;
+        if lock_graph.is_none() {
+            eprintln!("No lockfile found at {}", lockfile_path.display());
+        }
+        lock_graph
+    } else {
+        None
+    };
+    let members: HashSet<&str> = workspace.iter().map(|k| k.as_str()).collect();
+    let selected: HashSet<&str> = filtered.adjacency_list.keys().map(|k| k.as_str()).collect();
+    let external = lock_graph
+        .as_ref()
+        .filter(|_| args.external)
+        .map(|lock_graph| {
+            (
+                lock_graph.calculate_external_weights(&members, &selected),
+                lock_graph.direct_externals(&members, &selected),
+            )
+        });
+    let duplicates = lock_graph
+        .as_ref()
+        .filter(|_| args.duplicates)
+        .map(|lock_graph| lock_graph.find_duplicates(&members, &selected));
+
+    let destination = Destination::new(args.output.as_deref(), args.output_format);
 
-    // calculate and print the metrics
-    let metrics = filtered.calculate_coupling();
-    print_coupling(metrics);
+    // the report is left out if the diagram is written to stdout, so the diagram can be piped into other tools
+    if destination != Destination::Stdout {
+        match args.format {
+            ReportFormat::Table => {
+                print_counts(&graph, shown);
+                print_coupling(metrics, &levels);
+                if args.layers {
+                    print_layers(&levels);
+                }
+                print_cycles(&cycles);
+                if let Some(drift) = drift {
+                    print_version_drift(drift);
+                }
+                if let Some((weights, externals)) = external {
+                    print_external_dependencies(weights, externals);
+                }
+                if let Some(duplicates) = duplicates {
+                    print_duplicates(duplicates);
+                }
+                print_skipped_manifests(&diagnostics);
+            }
+            ReportFormat::Json => {
+                let mut report = Report::new(&graph, shown, metrics, &levels, &cycles)
+                    .with_skipped_manifests(diagnostics);
+                if let Some(drift) = drift {
+                    report = report.with_drift(drift);
+                }
+                if let Some((weights, externals)) = external {
+                    report = report.with_external(weights, externals);
+                }
+                if let Some(duplicates) = duplicates {
+                    report = report.with_duplicates(duplicates);
+                }
+                print_json_report(&report)?;
+            }
+        }
+    }
 
     // create diagram, incl. highlights of circular deps
-    let mmd = create_diagram(&filtered);
+    let diagram = match args.output_format {
+        FileExportOptions::DOT => generate_dot(shown, highlighted),
+        FileExportOptions::HTML => {
+            let report = Report::new(&graph, shown, shown.calculate_coupling(), &levels, &cycles);
+            generate_html(&report, &generate_svg(shown, highlighted))
+        }
+        FileExportOptions::SVG if !args.mmdc => generate_svg(shown, highlighted),
+        FileExportOptions::SVG | FileExportOptions::MMD => {
+            generate_mermaid_markdown(shown, highlighted, args.layers.then_some(&levels))
+        }
+    };
 
-    export(&mmd, args.output_format);
+    export(&diagram, args.output_format, args.mmdc, &destination)
 }
@@ -0,0 +1,133 @@
+use crate::error::{Error, Result};
+use ignore::gitignore::GitignoreBuilder;
+use ignore::overrides::{Override, OverrideBuilder};
+use ignore::Match;
+use std::path::{Path, PathBuf};
+
+/// Directories which never contain workspace members, but may contain copies of manifests, e.g. `cargo package` output.
+const PRUNED_DIRECTORIES: [&str; 4] = ["target", ".git", "node_modules", "vendor"];
+
+/// The directories to skip, given as globs relative to the workspace directory.
+pub struct ExcludedDirs {
+    root: PathBuf,
+    overrides: Override,
+}
+
+impl ExcludedDirs {
+    pub fn new(root: &Path, patterns: &[String]) -> Result<Self> {
+        let mut overrides = OverrideBuilder::new(root);
+        for pattern in patterns {
+            // a negated override excludes the matching paths
+            overrides
+                .add(&format!("!{}", pattern.trim_end_matches('/')))
+                .map_err(|e| {
+                    Error::Usage(format!(
+                        "invalid --exclude-dir pattern `{}`: {}",
+                        pattern, e
+                    ))
+                })?;
+        }
+        let overrides = overrides
+            .build()
+            .map_err(|e| Error::Usage(format!("invalid --exclude-dir pattern: {}", e)))?;
+
+        Ok(ExcludedDirs {
+            root: root.to_path_buf(),
+            overrides,
+        })
+    }
+
+    /// Returns whether the directory or one of its parents below the workspace directory is excluded.
+    pub fn contains(&self, dir: &Path) -> bool {
+        dir.ancestors()
+            .take_while(|ancestor| *ancestor != self.root)
+            .any(|ancestor| self.overrides.matched(ancestor, true).is_ignore())
+    }
+}
+
+/// Returns whether the directory below `root` lies within a known build directory or is ignored by the `.gitignore` or
+/// `.ignore` files of `root` and the directories in between. Like with git, the deepest file with a matching rule wins,
+/// and rules of `.ignore` files take precedence over those of `.gitignore` files.
+pub fn is_ignored(root: &Path, dir: &Path) -> bool {
+    let Ok(relative) = dir.strip_prefix(root) else {
+        return false;
+    };
+    if relative.components().any(|component| {
+        PRUNED_DIRECTORIES
+            .iter()
+            .any(|name| component.as_os_str() == *name)
+    }) {
+        return true;
+    }
+
+    // the ancestors of the directory up to and including the root
+    for ancestor in dir.ancestors().skip(1).take(relative.components().count()) {
+        let mut builder = GitignoreBuilder::new(ancestor);
+        for file in [".gitignore", ".ignore"] {
+            if ancestor.join(file).is_file() {
+                builder.add(ancestor.join(file));
+            }
+        }
+        let Ok(ignore) = builder.build() else {
+            continue;
+        };
+        match ignore.matched_path_or_any_parents(dir, true) {
+            Match::Ignore(_) => return true,
+            Match::Whitelist(_) => return false,
+            Match::None => {}
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_fs::prelude::*;
+    use assert_fs::TempDir;
+
+    #[test]
+    fn test_prune_build_directories() {
+        let root = Path::new("workspace");
+        assert!(is_ignored(root, &root.join("target/package/member-0.1.0")));
+        assert!(is_ignored(root, &root.join("web/node_modules/wasm")));
+        assert!(is_ignored(root, &root.join(".git/modules/member")));
+        assert!(is_ignored(root, &root.join("vendor/serde")));
+        assert!(!is_ignored(root, &root.join("crates/member")));
+    }
+
+    #[test]
+    fn test_honor_ignore_files() {
+        let temp_dir = TempDir::new().unwrap();
+        temp_dir
+            .child(".gitignore")
+            .write_str("generated/\n")
+            .unwrap();
+        temp_dir
+            .child("crates/.ignore")
+            .write_str("fixtures\n!generated\n")
+            .unwrap();
+
+        let root = temp_dir.path();
+        assert!(is_ignored(root, &root.join("generated/package")));
+        assert!(is_ignored(root, &root.join("crates/fixtures/one")));
+        // the deeper `.ignore` file re-includes the directory
+        assert!(!is_ignored(root, &root.join("crates/generated")));
+        assert!(!is_ignored(root, &root.join("crates/member")));
+    }
+
+    #[test]
+    fn test_exclude_dirs() {
+        let temp_dir = TempDir::new().unwrap();
+        let exclude_dirs = vec!["fixtures/".to_string(), "**/tests".to_string()];
+
+        let excluded = ExcludedDirs::new(temp_dir.path(), &exclude_dirs).unwrap();
+        assert!(excluded.contains(&temp_dir.path().join("fixtures/one")));
+        assert!(excluded.contains(&temp_dir.path().join("crates/member/tests/fixture")));
+        assert!(!excluded.contains(&temp_dir.path().join("crates/member")));
+        assert!(matches!(
+            ExcludedDirs::new(temp_dir.path(), &["[".to_string()]),
+            Err(Error::Usage(_))
+        ));
+    }
+}
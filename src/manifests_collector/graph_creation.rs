@@ -1,9 +1,14 @@
-use crate::graph::Graph;
-use crate::types::nested::ManifestFindings;
+use crate::graph::{DependencyKind, EdgeInfo, Graph};
+use crate::types::commons::{Dependencies, DependencyInfo};
+use crate::types::nested::{Manifest, ManifestFindings};
 
 impl Graph {
     /// Populates the Graph from the root manifest and nested manifests (dependencies).
-    pub fn new_from_manifests(nested: &ManifestFindings) -> Self {
+    /// The workspace dependencies are used to resolve inherited dependencies.
+    pub fn new_from_manifests(
+        nested: &ManifestFindings,
+        workspace_dependencies: Option<&Dependencies>,
+    ) -> Self {
         let mut graph = Graph::new();
 
         // Add nested package dependencies to the map
@@ -11,26 +16,111 @@ impl Graph {
             let package_name = &manifest_finding.manifest.package.name;
             let mut dependencies = Vec::new();
 
-            if let Some(package_dependencies) = &manifest_finding.manifest.dependencies {
-                for dep_name in package_dependencies.keys() {
-                    dependencies.push(dep_name.clone());
+            for (kind, target, package_dependencies) in
+                dependency_tables(&manifest_finding.manifest)
+            {
+                for (dep_key, dep_info) in package_dependencies {
+                    let (dep_name, info) =
+                        resolve_dependency(dep_key, dep_info, workspace_dependencies);
+                    graph
+                        .edge_infos
+                        .entry((package_name.clone(), dep_name.clone()))
+                        .or_default()
+                        .push(EdgeInfo {
+                            kind,
+                            target: target.map(|t| t.to_string()),
+                            ..info
+                        });
+                    dependencies.push(dep_name);
                 }
-                dependencies.sort();
             }
+            dependencies.sort();
+            dependencies.dedup();
 
             graph
                 .adjacency_list
                 .insert(package_name.clone(), dependencies);
+
+            if let Some(features) = &manifest_finding.manifest.features {
+                graph
+                    .features
+                    .insert(package_name.clone(), features.clone());
+            }
         }
 
         graph
     }
 }
 
+/// Resolves the name of the package a dependency refers to, together with the details of the declaration.
+/// If the dependency is renamed, the key it is declared with is kept as its alias.
+fn resolve_dependency(
+    key: &str,
+    info: &DependencyInfo,
+    workspace_dependencies: Option<&Dependencies>,
+) -> (String, EdgeInfo) {
+    let inherited = if info.is_inherited() {
+        workspace_dependencies.and_then(|deps| deps.get(key))
+    } else {
+        None
+    };
+
+    let (name, alias) = match inherited.unwrap_or(info).renamed_package() {
+        Some(package) if package != key => (package.to_string(), Some(key.to_string())),
+        _ => (key.to_string(), None),
+    };
+
+    // Features enabled by the member are added to the ones of the workspace dependency
+    let mut features = inherited
+        .map(|workspace_info| workspace_info.features().to_vec())
+        .unwrap_or_default();
+    features.extend(info.features().iter().cloned());
+
+    let info = EdgeInfo {
+        alias,
+        optional: info.is_optional(),
+        features,
+        no_default_features: inherited.unwrap_or(info).is_without_default_features(),
+        version: inherited
+            .unwrap_or(info)
+            .version()
+            .map(|version| version.to_string()),
+        inherited: inherited.is_some(),
+        ..Default::default()
+    };
+    (name, info)
+}
+
+/// Returns all dependency tables of a manifest together with their kind
+/// and, for the tables within `[target.<spec>]`, the platform specification.
+pub(super) fn dependency_tables(
+    manifest: &Manifest,
+) -> Vec<(DependencyKind, Option<&str>, &Dependencies)> {
+    let mut tables = vec![
+        (DependencyKind::Normal, None, &manifest.dependencies),
+        (DependencyKind::Build, None, &manifest.build_dependencies),
+        (DependencyKind::Dev, None, &manifest.dev_dependencies),
+    ];
+
+    if let Some(targets) = &manifest.target {
+        for (spec, platform) in targets {
+            let spec = Some(spec.as_str());
+            tables.push((DependencyKind::Normal, spec, &platform.dependencies));
+            tables.push((DependencyKind::Build, spec, &platform.build_dependencies));
+            tables.push((DependencyKind::Dev, spec, &platform.dev_dependencies));
+        }
+    }
+
+    tables
+        .into_iter()
+        .filter_map(|(kind, spec, table)| table.as_ref().map(|table| (kind, spec, table)))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::commons::{DependencyInfo, Package};
+    use crate::types::commons::{DependencyInfo, DetailedDependency, Package};
     use crate::types::nested::{Manifest, ManifestFinding};
     use std::collections::HashMap;
     use std::path::PathBuf;
@@ -52,6 +142,7 @@ mod tests {
                 } else {
                     Some(dependency_map)
                 },
+                ..Default::default()
             },
         }
     }
@@ -60,7 +151,7 @@ mod tests {
     fn test_single_package_no_dependencies() {
         // A single package with no dependencies
         let nested = vec![setup_manifest("package_a", vec![])];
-        let graph = Graph::new_from_manifests(&nested);
+        let graph = Graph::new_from_manifests(&nested, None);
         let adj_list = HashMap::from([("package_a".to_string(), vec![])]);
         assert_eq!(graph.adjacency_list, adj_list);
     }
@@ -69,7 +160,7 @@ mod tests {
     fn test_single_package_with_dependencies() {
         // A single package with dependencies
         let nested = vec![setup_manifest("package_a", vec!["package_b", "package_c"])];
-        let graph = Graph::new_from_manifests(&nested);
+        let graph = Graph::new_from_manifests(&nested, None);
         let expected_graph = HashMap::from([(
             "package_a".to_string(),
             vec!["package_b".to_string(), "package_c".to_string()],
@@ -86,7 +177,7 @@ mod tests {
             setup_manifest("package_c", vec![]),
         ];
 
-        let graph = Graph::new_from_manifests(&nested);
+        let graph = Graph::new_from_manifests(&nested, None);
         let expected_graph = HashMap::from([
             ("package_a".to_string(), vec!["package_b".to_string()]),
             ("package_b".to_string(), vec!["package_c".to_string()]),
@@ -105,7 +196,7 @@ mod tests {
             setup_manifest("package_c", vec![]),
         ];
 
-        let graph = Graph::new_from_manifests(&nested);
+        let graph = Graph::new_from_manifests(&nested, None);
         let expected_graph = HashMap::from([
             ("package_a".to_string(), vec![]),
             ("package_b".to_string(), vec![]),
@@ -126,7 +217,7 @@ mod tests {
             setup_manifest("package_e", vec![]),
         ];
 
-        let graph = Graph::new_from_manifests(&nested);
+        let graph = Graph::new_from_manifests(&nested, None);
         let expected_graph = HashMap::from([
             (
                 "package_a".to_string(),
@@ -143,4 +234,124 @@ mod tests {
 
         assert_eq!(graph.adjacency_list, expected_graph);
     }
+
+    #[test]
+    fn test_dependency_kinds() {
+        let mut finding = setup_manifest("package_a", vec!["package_b"]);
+        finding.manifest.dev_dependencies = Some(HashMap::from([
+            (
+                "package_b".to_string(),
+                DependencyInfo::Simple("1.0".to_string()),
+            ),
+            (
+                "package_c".to_string(),
+                DependencyInfo::Simple("1.0".to_string()),
+            ),
+        ]));
+        finding.manifest.build_dependencies = Some(HashMap::from([(
+            "package_d".to_string(),
+            DependencyInfo::Simple("1.0".to_string()),
+        )]));
+
+        let graph = Graph::new_from_manifests(&vec![finding], None);
+
+        // Every target is only listed once, even if it is declared in several tables
+        assert_eq!(
+            graph.adjacency_list["package_a"],
+            vec!["package_b", "package_c", "package_d"]
+        );
+        assert_eq!(graph.get_edge_infos("package_a", "package_b").len(), 2);
+        assert_eq!(
+            graph.get_edge_kind("package_a", "package_b"),
+            DependencyKind::Normal
+        );
+        assert_eq!(
+            graph.get_edge_kind("package_a", "package_c"),
+            DependencyKind::Dev
+        );
+        assert_eq!(
+            graph.get_edge_kind("package_a", "package_d"),
+            DependencyKind::Build
+        );
+    }
+
+    #[test]
+    fn test_renamed_dependencies() {
+        let mut finding = setup_manifest("app", vec![]);
+        finding.manifest.dependencies = Some(HashMap::from([
+            (
+                "core".to_string(),
+                DependencyInfo::Detailed(DetailedDependency {
+                    path: Some("../core".to_string()),
+                    package: Some("acme-core".to_string()),
+                    ..Default::default()
+                }),
+            ),
+            (
+                "log".to_string(),
+                DependencyInfo::Detailed(DetailedDependency {
+                    workspace: Some(true),
+                    ..Default::default()
+                }),
+            ),
+        ]));
+        let workspace_dependencies = HashMap::from([(
+            "log".to_string(),
+            DependencyInfo::Detailed(DetailedDependency {
+                path: Some("crates/log".to_string()),
+                package: Some("acme-log".to_string()),
+                ..Default::default()
+            }),
+        )]);
+
+        let graph = Graph::new_from_manifests(&vec![finding], Some(&workspace_dependencies));
+
+        // The edges point to the actual packages and keep the alias
+        assert_eq!(graph.adjacency_list["app"], vec!["acme-core", "acme-log"]);
+        assert_eq!(graph.get_edge_aliases("app", "acme-core"), vec!["core"]);
+        assert_eq!(graph.get_edge_aliases("app", "acme-log"), vec!["log"]);
+    }
+
+    #[test]
+    fn test_target_specific_dependencies() {
+        let manifest: Manifest = toml::from_str(
+            r#"
+            [package]
+            name = "app"
+
+            [dependencies]
+            core = { path = "../core" }
+
+            [target.'cfg(unix)'.dependencies]
+            sys-unix = { path = "../sys-unix" }
+            core = { path = "../core" }
+
+            [target.x86_64-pc-windows-msvc.dev-dependencies]
+            sys-windows = { path = "../sys-windows" }
+            "#,
+        )
+        .unwrap();
+        let finding = ManifestFinding {
+            path: PathBuf::from("app"),
+            manifest,
+        };
+
+        let graph = Graph::new_from_manifests(&vec![finding], None);
+
+        assert_eq!(
+            graph.adjacency_list["app"],
+            vec!["core", "sys-unix", "sys-windows"]
+        );
+        // The unconditional declaration wins over the target specific one
+        assert!(graph.get_edge_targets("app", "core").is_empty());
+        assert_eq!(graph.get_edge_targets("app", "sys-unix"), vec!["cfg(unix)"]);
+        assert_eq!(
+            graph.get_edge_targets("app", "sys-windows"),
+            vec!["x86_64-pc-windows-msvc"]
+        );
+        assert_eq!(
+            graph.get_edge_kind("app", "sys-windows"),
+            DependencyKind::Dev
+        );
+    }
 }
@@ -0,0 +1,527 @@
+use crate::error::{Diagnostic, Error, Result};
+use crate::manifests_collector::discovery::{is_ignored, ExcludedDirs};
+use crate::manifests_collector::graph_creation::dependency_tables;
+use crate::manifests_collector::reader::load_cargo_toml_content;
+use crate::manifests_collector::root::find_workspace_root;
+use crate::types::commons::Dependencies;
+use crate::types::nested::{ManifestFinding, ManifestFindings};
+use crate::types::root::CargoRootManifestFinding;
+use glob::glob;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// Reads the manifests of the members declared in `workspace.members`.
+/// Globs are expanded relative to the workspace root. Members listed in `workspace.exclude` or within one of the
+/// `exclude_dirs` are left out. Manifests which cannot be parsed and declared members which don't exist on disk are
+/// reported as diagnostics.
+///
+/// A package defined by the root manifest is a member as well. If the root manifest defines only a package, its path
+/// dependencies are collected instead of the declared members.
+pub fn collect_members(
+    root_manifest_finding: &CargoRootManifestFinding,
+    exclude_dirs: &[String],
+) -> Result<(ManifestFindings, Vec<Diagnostic>)> {
+    let root_dir = &root_manifest_finding.path;
+    let mut packages = Vec::new();
+    let mut diagnostics = Vec::new();
+    let mut seen = HashSet::new();
+
+    if root_manifest_finding.manifest.package.is_some() {
+        let manifest = load_cargo_toml_content(&root_dir.join("Cargo.toml"))?;
+        seen.insert(normalize_path(root_dir));
+        packages.push(ManifestFinding {
+            path: root_dir.clone(),
+            manifest,
+        });
+    }
+
+    let Some(workspace) = &root_manifest_finding.manifest.workspace else {
+        let (dependencies, dependency_diagnostics) =
+            collect_path_dependencies(&packages, root_dir, None);
+        packages.extend(dependencies);
+        diagnostics.extend(dependency_diagnostics);
+        return Ok((packages, diagnostics));
+    };
+
+    let excludes: Vec<PathBuf> = workspace
+        .exclude
+        .iter()
+        .flatten()
+        .map(|exclude| normalize_path(&root_dir.join(exclude)))
+        .collect();
+    let excluded_dirs = ExcludedDirs::new(root_dir, exclude_dirs)?;
+
+    for member_dir in resolve_member_dirs(root_dir, workspace.members.iter().flatten())? {
+        let normalized_path = normalize_path(&member_dir);
+        if excludes
+            .iter()
+            .any(|exclude| normalized_path.starts_with(exclude))
+            || excluded_dirs.contains(&member_dir)
+            || !seen.insert(normalized_path)
+        {
+            continue;
+        }
+
+        let cargo_toml_path = member_dir.join("Cargo.toml");
+        if !cargo_toml_path.exists() {
+            diagnostics.push(Diagnostic {
+                path: cargo_toml_path,
+                reason: "declared in workspace.members, but doesn't exist".to_string(),
+            });
+            continue;
+        }
+
+        match load_cargo_toml_content(&cargo_toml_path) {
+            Ok(manifest) => packages.push(ManifestFinding {
+                path: member_dir,
+                manifest,
+            }),
+            Err(error) => diagnostics.push(Diagnostic::new(&cargo_toml_path, &error)),
+        }
+    }
+
+    Ok((packages, diagnostics))
+}
+
+/// Returns the packages which are reachable from the given ones via path dependencies, following them transitively.
+/// The given packages aren't returned again. Manifests which cannot be read are reported as diagnostics.
+///
+/// Dependencies inherited via `workspace = true` are looked up in the `workspace_dependencies` of the root, whose
+/// paths are relative to `root_dir`. Packages outside of `root_dir` inherit them from their own workspace instead.
+/// Inherited dependencies which the workspace doesn't declare are reported as diagnostics.
+pub fn collect_path_dependencies(
+    packages: &ManifestFindings,
+    root_dir: &Path,
+    workspace_dependencies: Option<&Dependencies>,
+) -> (ManifestFindings, Vec<Diagnostic>) {
+    let mut seen: HashSet<PathBuf> = packages
+        .iter()
+        .map(|package| normalize_path(&package.path))
+        .collect();
+    let mut found: ManifestFindings = Vec::new();
+    let mut diagnostics = Vec::new();
+
+    let mut next = 0;
+    while let Some(package) = packages
+        .get(next)
+        .or_else(|| found.get(next - packages.len()))
+    {
+        let own_workspace;
+        let (workspace_dir, workspace_dependencies) = if is_within(root_dir, &package.path) {
+            (root_dir, workspace_dependencies)
+        } else {
+            own_workspace = find_workspace_root(&package.path);
+            match &own_workspace {
+                Some(workspace) => (
+                    workspace.path.as_path(),
+                    workspace.manifest.workspace_dependencies(),
+                ),
+                None => (root_dir, None),
+            }
+        };
+
+        let mut dependency_dirs = Vec::new();
+        for (_, _, dependencies) in dependency_tables(&package.manifest) {
+            for (key, info) in dependencies {
+                if info.is_inherited() {
+                    match workspace_dependencies.and_then(|dependencies| dependencies.get(key)) {
+                        Some(workspace_info) => {
+                            if let Some(path) = workspace_info.path() {
+                                dependency_dirs.push(workspace_dir.join(path));
+                            }
+                        }
+                        None => diagnostics.push(Diagnostic {
+                            path: package.path.join("Cargo.toml"),
+                            reason: format!(
+                                "inherits `{}` from its workspace, which doesn't declare it",
+                                key
+                            ),
+                        }),
+                    }
+                } else if let Some(path) = info.path() {
+                    dependency_dirs.push(package.path.join(path));
+                }
+            }
+        }
+        dependency_dirs.sort();
+        next += 1;
+
+        for dir in dependency_dirs {
+            if !seen.insert(normalize_path(&dir)) {
+                continue;
+            }
+            let cargo_toml_path = dir.join("Cargo.toml");
+            match load_cargo_toml_content(&cargo_toml_path) {
+                Ok(manifest) => found.push(ManifestFinding {
+                    path: dir,
+                    manifest,
+                }),
+                Err(error) => diagnostics.push(Diagnostic::new(&cargo_toml_path, &error)),
+            }
+        }
+    }
+
+    (found, diagnostics)
+}
+
+/// Returns the names of the members which `cargo build` selects when no package is given.
+/// Without `workspace.default-members`, these are the root package if there is one, otherwise all members.
+pub fn default_member_names(
+    root_manifest_finding: &CargoRootManifestFinding,
+    members: &ManifestFindings,
+) -> Result<Vec<String>> {
+    let root_dir = &root_manifest_finding.path;
+    let manifest = &root_manifest_finding.manifest;
+    let default_members = manifest
+        .workspace
+        .as_ref()
+        .and_then(|workspace| workspace.default_members.as_ref());
+
+    let selected = |dir: &Path| {
+        let normalized_path = normalize_path(dir);
+        members
+            .iter()
+            .filter(move |member| normalize_path(&member.path) == normalized_path)
+            .map(|member| member.manifest.package.name.clone())
+    };
+
+    let names = match (default_members, &manifest.package) {
+        (Some(default_members), _) => resolve_member_dirs(root_dir, default_members.iter())?
+            .iter()
+            .flat_map(|dir| selected(dir))
+            .collect(),
+        (None, Some(_)) => selected(root_dir).collect(),
+        (None, None) => members
+            .iter()
+            .map(|member| member.manifest.package.name.clone())
+            .collect(),
+    };
+    Ok(names)
+}
+
+/// Returns the directories of the declared members. Paths matched by a glob are only included if they contain a
+/// manifest and are neither ignored nor within a build directory, while members given as plain paths are always
+/// included.
+fn resolve_member_dirs<'a>(
+    root_dir: &Path,
+    members: impl Iterator<Item = &'a String>,
+) -> Result<Vec<PathBuf>> {
+    let mut dirs = Vec::new();
+
+    for member in members {
+        let full_path = root_dir.join(member);
+
+        if member.contains('*') || member.contains('?') || member.contains('[') {
+            // Handle wildcard pattern
+            let pattern = full_path.to_string_lossy().to_string();
+            let entries = glob(&pattern).map_err(|source| Error::InvalidGlob {
+                pattern: member.clone(),
+                source,
+            })?;
+            let mut matches: Vec<PathBuf> = entries
+                .flatten()
+                .filter(|entry| entry.join("Cargo.toml").is_file() && !is_ignored(root_dir, entry))
+                .collect();
+            matches.sort();
+            dirs.extend(matches);
+        } else {
+            dirs.push(full_path);
+        }
+    }
+
+    Ok(dirs)
+}
+
+/// Whether `path` lies within `root_dir`, in which case Cargo treats a package there as a member of the workspace.
+pub fn is_within(root_dir: &Path, path: &Path) -> bool {
+    normalize_path(path).starts_with(normalize_path(root_dir))
+}
+
+fn normalize_path(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::commons::Package;
+    use crate::types::root::{CargoRootManifest, Workspace};
+    use assert_fs::prelude::*;
+    use assert_fs::TempDir;
+
+    fn create_manifest(dir: &TempDir, path: &str, name: &str) {
+        dir.child(path)
+            .child("Cargo.toml")
+            .write_str(&format!("[package]\nname = \"{}\"\n", name))
+            .unwrap();
+    }
+
+    fn root_finding(dir: &TempDir, members: &[&str], exclude: &[&str]) -> CargoRootManifestFinding {
+        CargoRootManifestFinding {
+            path: dir.path().to_path_buf(),
+            manifest: CargoRootManifest {
+                package: None,
+                workspace: Some(Workspace {
+                    members: Some(members.iter().map(|m| m.to_string()).collect()),
+                    exclude: Some(exclude.iter().map(|e| e.to_string()).collect()),
+                    ..Default::default()
+                }),
+            },
+        }
+    }
+
+    fn names(findings: &ManifestFindings) -> Vec<&str> {
+        findings
+            .iter()
+            .map(|f| f.manifest.package.name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_collect_declared_members() {
+        let temp_workspace = TempDir::new().unwrap();
+        create_manifest(&temp_workspace, "member1", "member1");
+        create_manifest(&temp_workspace, "member2", "member2");
+        create_manifest(&temp_workspace, "member3", "member3");
+        create_manifest(&temp_workspace, "member4", "member4");
+
+        let root = root_finding(
+            &temp_workspace,
+            &["member1", "member2", "member4"],
+            &["member4"],
+        );
+        let (members, diagnostics) = collect_members(&root, &[]).unwrap();
+
+        // members don't need a `src` directory, e.g. if their library lives at a custom `[lib] path`
+        assert_eq!(names(&members), vec!["member1", "member2"]);
+        assert!(diagnostics.is_empty());
+    }
+
+    #[test]
+    fn test_collect_members_with_wildcards() {
+        let temp_workspace = TempDir::new().unwrap();
+        create_manifest(&temp_workspace, "crates/crate1", "crate1");
+        create_manifest(&temp_workspace, "crates/crate2", "crate2");
+        create_manifest(&temp_workspace, "crates/nested/crate3", "crate3");
+        create_manifest(&temp_workspace, "crates/fixture", "fixture");
+        // directories matched by a glob without a manifest are not members
+        temp_workspace
+            .child("crates/docs")
+            .create_dir_all()
+            .unwrap();
+
+        let root = root_finding(&temp_workspace, &["crates/*"], &["crates/fixture"]);
+        let (members, diagnostics) = collect_members(&root, &[]).unwrap();
+
+        assert_eq!(names(&members), vec!["crate1", "crate2"]);
+        assert!(diagnostics.is_empty());
+
+        let (members, _) = collect_members(&root, &["crates/crate2".to_string()]).unwrap();
+        assert_eq!(names(&members), vec!["crate1"]);
+    }
+
+    #[test]
+    fn test_skip_ignored_and_build_directories() {
+        let temp_workspace = TempDir::new().unwrap();
+        create_manifest(&temp_workspace, "crates/app", "app");
+        create_manifest(&temp_workspace, "crates/target/package/x", "x");
+        create_manifest(&temp_workspace, "crates/vendor/serde", "serde");
+        create_manifest(&temp_workspace, "crates/generated/template", "template");
+        temp_workspace
+            .child(".gitignore")
+            .write_str("generated/\n")
+            .unwrap();
+
+        let root = root_finding(&temp_workspace, &["crates/**"], &[]);
+        let (members, diagnostics) = collect_members(&root, &[]).unwrap();
+
+        assert_eq!(names(&members), vec!["app"]);
+        assert!(diagnostics.is_empty());
+    }
+
+    #[test]
+    fn test_report_missing_and_invalid_members() {
+        let temp_workspace = TempDir::new().unwrap();
+        create_manifest(&temp_workspace, "member1", "member1");
+        temp_workspace
+            .child("template/Cargo.toml")
+            .write_str("[package]\nname = {{name}}\n")
+            .unwrap();
+
+        let root = root_finding(&temp_workspace, &["member1", "missing", "template"], &[]);
+        let (members, diagnostics) = collect_members(&root, &[]).unwrap();
+
+        assert_eq!(names(&members), vec!["member1"]);
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(
+            diagnostics[0].path,
+            temp_workspace.path().join("missing/Cargo.toml")
+        );
+        assert_eq!(
+            diagnostics[0].reason,
+            "declared in workspace.members, but doesn't exist"
+        );
+        assert!(diagnostics[1].reason.starts_with("line 2, column 9"));
+    }
+
+    #[test]
+    fn test_root_package_is_member() {
+        let temp_workspace = TempDir::new().unwrap();
+        temp_workspace
+            .child("Cargo.toml")
+            .write_str("[package]\nname = \"root\"\n[workspace]\nmembers = [\".\", \"member1\"]\n")
+            .unwrap();
+        create_manifest(&temp_workspace, "member1", "member1");
+
+        let mut root = root_finding(&temp_workspace, &[".", "member1"], &[]);
+        root.manifest.package = Some(Package {
+            name: "root".to_string(),
+        });
+        let (members, diagnostics) = collect_members(&root, &[]).unwrap();
+
+        // the root package is only included once, even though it is declared as member
+        assert_eq!(names(&members), vec!["root", "member1"]);
+        assert!(diagnostics.is_empty());
+    }
+
+    #[test]
+    fn test_single_package_with_path_dependencies() {
+        let temp_dir = TempDir::new().unwrap();
+        temp_dir
+            .child("app/Cargo.toml")
+            .write_str("[package]\nname = \"app\"\n[dependencies]\nutil = { path = \"../util\" }\nserde = \"1\"\n")
+            .unwrap();
+        temp_dir
+            .child("util/Cargo.toml")
+            .write_str("[package]\nname = \"util\"\n[dev-dependencies]\napp = { path = \"../app\" }\nmissing = { path = \"../missing\" }\n")
+            .unwrap();
+
+        let root = CargoRootManifestFinding {
+            path: temp_dir.path().join("app"),
+            manifest: CargoRootManifest {
+                package: Some(Package {
+                    name: "app".to_string(),
+                }),
+                workspace: None,
+            },
+        };
+        let (members, diagnostics) = collect_members(&root, &[]).unwrap();
+
+        assert_eq!(names(&members), vec!["app", "util"]);
+        assert_eq!(diagnostics.len(), 1);
+        assert!(diagnostics[0].path.ends_with("missing/Cargo.toml"));
+    }
+
+    #[test]
+    fn test_default_member_names() {
+        let temp_workspace = TempDir::new().unwrap();
+        create_manifest(&temp_workspace, "crates/cli", "cli");
+        create_manifest(&temp_workspace, "crates/core", "core");
+
+        let mut root = root_finding(&temp_workspace, &["crates/*"], &[]);
+        let (members, _) = collect_members(&root, &[]).unwrap();
+        assert_eq!(
+            default_member_names(&root, &members).unwrap(),
+            vec!["cli", "core"]
+        );
+
+        root.manifest.workspace.as_mut().unwrap().default_members =
+            Some(vec!["crates/cli".to_string(), "missing".to_string()]);
+        assert_eq!(default_member_names(&root, &members).unwrap(), vec!["cli"]);
+    }
+
+    #[test]
+    fn test_collect_path_dependencies_outside_the_workspace() {
+        let temp_dir = TempDir::new().unwrap();
+        let workspace = temp_dir.child("workspace");
+        workspace
+            .child("app/Cargo.toml")
+            .write_str("[package]\nname = \"app\"\n[dependencies]\ncore = { path = \"../core\" }\nshared = { workspace = true }\n")
+            .unwrap();
+        workspace
+            .child("core/Cargo.toml")
+            .write_str("[package]\nname = \"core\"\n[dependencies]\nlog = { path = \"../../shared/log\" }\n")
+            .unwrap();
+        temp_dir
+            .child("shared/foo/Cargo.toml")
+            .write_str(
+                "[package]\nname = \"shared\"\n[dependencies]\nlog = { path = \"../log\" }\n",
+            )
+            .unwrap();
+        temp_dir
+            .child("shared/log/Cargo.toml")
+            .write_str("[package]\nname = \"log\"\n")
+            .unwrap();
+
+        let finding = CargoRootManifestFinding {
+            path: workspace.path().to_path_buf(),
+            manifest: CargoRootManifest {
+                package: None,
+                workspace: Some(Workspace {
+                    members: Some(vec!["app".to_string(), "core".to_string()]),
+                    ..Default::default()
+                }),
+            },
+        };
+        let (members, _) = collect_members(&finding, &[]).unwrap();
+
+        let workspace_dependencies: Dependencies =
+            toml::from_str("shared = { path = \"../shared/foo\" }").unwrap();
+        let (external_paths, diagnostics) =
+            collect_path_dependencies(&members, workspace.path(), Some(&workspace_dependencies));
+
+        // `log` is reached twice, but only collected once
+        assert_eq!(names(&external_paths), vec!["shared", "log"]);
+        assert!(diagnostics.is_empty());
+    }
+
+    #[test]
+    fn test_collect_path_dependencies_inherit_from_their_own_workspace() {
+        let temp_dir = TempDir::new().unwrap();
+        let workspace = temp_dir.child("workspace");
+        workspace
+            .child("app/Cargo.toml")
+            .write_str("[package]\nname = \"app\"\n[dependencies]\nfoo = { path = \"../../shared/foo\" }\n")
+            .unwrap();
+        temp_dir
+            .child("shared/Cargo.toml")
+            .write_str("[workspace]\nmembers = [\"foo\"]\n[workspace.dependencies]\nlog = { path = \"log\" }\n")
+            .unwrap();
+        temp_dir
+            .child("shared/foo/Cargo.toml")
+            .write_str("[package]\nname = \"foo\"\n[dependencies]\nlog = { workspace = true }\nutil = { workspace = true }\n")
+            .unwrap();
+        temp_dir
+            .child("shared/log/Cargo.toml")
+            .write_str("[package]\nname = \"log\"\n")
+            .unwrap();
+
+        let finding = CargoRootManifestFinding {
+            path: workspace.path().to_path_buf(),
+            manifest: CargoRootManifest {
+                package: None,
+                workspace: Some(Workspace {
+                    members: Some(vec!["app".to_string()]),
+                    ..Default::default()
+                }),
+            },
+        };
+        let (members, _) = collect_members(&finding, &[]).unwrap();
+
+        // the workspace being analyzed declares `log` as well, which must not be used by `foo`
+        let workspace_dependencies: Dependencies =
+            toml::from_str("log = { path = \"../wrong/log\" }\nutil = { path = \"util\" }")
+                .unwrap();
+        let (external_paths, diagnostics) =
+            collect_path_dependencies(&members, workspace.path(), Some(&workspace_dependencies));
+
+        assert_eq!(names(&external_paths), vec!["foo", "log"]);
+        assert!(external_paths[1].path.ends_with("shared/log"));
+        assert_eq!(diagnostics.len(), 1);
+        assert!(diagnostics[0].path.ends_with("shared/foo/Cargo.toml"));
+        assert_eq!(
+            diagnostics[0].reason,
+            "inherits `util` from its workspace, which doesn't declare it"
+        );
+    }
+}
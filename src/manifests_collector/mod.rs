@@ -1,18 +1,51 @@
+use crate::error::{Diagnostic, Result};
 use crate::graph::Graph;
-use crate::manifests_collector::filter::filter_findings_by_workspace;
-use crate::manifests_collector::nested::collect_manifests;
-use crate::manifests_collector::root::get_root_manifest;
+use crate::manifests_collector::members::{
+    collect_members, collect_path_dependencies, default_member_names, is_within,
+};
+use crate::manifests_collector::root::{find_workspace_root, get_root_manifest};
+use crate::types::nested::ManifestFindings;
 use std::path::Path;
 
-mod filter;
-mod graph_creation;
-mod nested;
-mod reader;
+pub(crate) mod discovery;
+pub(crate) mod graph_creation;
+mod members;
+pub(crate) mod reader;
 mod root;
 
-pub fn get_dependency_graph(dir: &Path) -> Graph {
-    let root_finding = get_root_manifest(dir);
-    let nested = collect_manifests(dir);
-    let filtered = filter_findings_by_workspace(&root_finding, nested);
-    Graph::new_from_manifests(&filtered)
+/// Builds the graph of the workspace in the directory from the manifests of its declared members.
+/// Packages which members reach via path dependencies are members as well if they are located within the workspace
+/// directory, like Cargo treats them. Packages outside of it are added as external path packages, which inherit
+/// dependencies from their own workspace.
+/// Returns the names of the default members as well as the diagnostics of the members which were skipped, since they
+/// don't exist or could not be parsed.
+pub fn get_dependency_graph(
+    dir: &Path,
+    exclude_dirs: &[String],
+) -> Result<(Graph, Vec<String>, Vec<Diagnostic>)> {
+    let root_finding = get_root_manifest(dir)?;
+    let (mut members, mut diagnostics) = collect_members(&root_finding, exclude_dirs)?;
+
+    let workspace_dependencies = root_finding.manifest.workspace_dependencies();
+    let (path_dependencies, path_dependency_diagnostics) =
+        collect_path_dependencies(&members, &root_finding.path, workspace_dependencies);
+    let (implicit_members, external_paths): (ManifestFindings, ManifestFindings) =
+        path_dependencies
+            .into_iter()
+            .partition(|package| is_within(&root_finding.path, &package.path));
+    members.extend(implicit_members);
+    diagnostics.extend(path_dependency_diagnostics);
+    let default_members = default_member_names(&root_finding, &members)?;
+
+    let mut graph = Graph::new_from_manifests(&members, workspace_dependencies);
+    for package in external_paths {
+        let name = package.manifest.package.name.clone();
+        let own_workspace = find_workspace_root(&package.path);
+        let own_dependencies = own_workspace
+            .as_ref()
+            .and_then(|workspace| workspace.manifest.workspace_dependencies());
+        graph.extend(Graph::new_from_manifests(&vec![package], own_dependencies));
+        graph.external_paths.insert(name);
+    }
+    Ok((graph, default_members, diagnostics))
 }
@@ -1,26 +1,18 @@
+use crate::error::{Error, Result};
 use serde::de::DeserializeOwned;
 use std::fs;
 use std::path::Path;
 
-pub fn load_cargo_toml_content<T>(path: &Path) -> Option<T>
+pub fn load_cargo_toml_content<T>(path: &Path) -> Result<T>
 where
     T: DeserializeOwned,
 {
     // Attempt to read the file
-    let content = match fs::read_to_string(path) {
-        Ok(content) => content,
-        Err(e) => {
-            eprintln!("Failed to read {}: {}", path.display(), e);
-            return None;
-        }
-    };
+    let content = fs::read_to_string(path).map_err(|source| Error::Read {
+        path: path.to_path_buf(),
+        source,
+    })?;
 
     // Attempt to parse the TOML content
-    match toml::from_str(&content) {
-        Ok(parsed) => Some(parsed),
-        Err(e) => {
-            eprintln!("Failed to parse {}: {}", path.display(), e);
-            None
-        }
-    }
+    toml::from_str(&content).map_err(|source| Error::toml(path, &content, source))
 }
@@ -1,28 +1,45 @@
 use std::path::Path;
 
+use crate::error::{Error, Result};
 use crate::manifests_collector::reader::load_cargo_toml_content;
 use crate::types::root::{CargoRootManifest, CargoRootManifestFinding};
 
 /// Reads the contents of `Cargo.toml` in the specified directory.
-/// Panics if the file does not exist or cannot be read.
-pub fn get_root_manifest(dir: &Path) -> CargoRootManifestFinding {
+/// Fails if the file does not exist, cannot be read or defines neither a workspace nor a package.
+pub fn get_root_manifest(dir: &Path) -> Result<CargoRootManifestFinding> {
     let cargo_toml_path = dir.join("Cargo.toml");
 
     if cargo_toml_path.exists() {
-        let manifest = load_cargo_toml_content::<CargoRootManifest>(&cargo_toml_path)
-            .expect("Failed to parse root manifest");
-        if manifest.workspace.is_none() {
-            panic!("This directory doesn't seem to be a workspace. There is no workspace definition in the root manifest. ");
+        let manifest = load_cargo_toml_content::<CargoRootManifest>(&cargo_toml_path)?;
+        if manifest.workspace.is_none() && manifest.package.is_none() {
+            return Err(Error::NotAWorkspace(cargo_toml_path));
         }
-        CargoRootManifestFinding {
+        Ok(CargoRootManifestFinding {
             manifest,
             path: dir.to_path_buf(),
-        }
+        })
     } else {
-        panic!("No Cargo.toml file found in the specified directory");
+        Err(Error::MissingManifest(dir.to_path_buf()))
     }
 }
 
+/// Returns the root manifest of the workspace which the package in `dir` belongs to, i.e. the nearest manifest in
+/// `dir` or one of its parents which defines a workspace. Manifests which cannot be read are passed over.
+pub fn find_workspace_root(dir: &Path) -> Option<CargoRootManifestFinding> {
+    let dir = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+    dir.ancestors().find_map(|ancestor| {
+        let manifest =
+            load_cargo_toml_content::<CargoRootManifest>(&ancestor.join("Cargo.toml")).ok()?;
+        manifest
+            .workspace
+            .is_some()
+            .then(|| CargoRootManifestFinding {
+                manifest,
+                path: ancestor.to_path_buf(),
+            })
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -43,7 +60,7 @@ mod tests {
 
         // Test that the function reads the file correctly
         // Pass the full path to Cargo.toml to get_root_manifest
-        let manifest = get_root_manifest(&temp_dir.path());
+        let manifest = get_root_manifest(temp_dir.path()).unwrap();
 
         // Check that the `package` section exists and has the expected values
         if let Some(manifest) = manifest.manifest.workspace {
@@ -54,12 +71,35 @@ mod tests {
     }
 
     #[test]
-    #[should_panic]
     fn test_read_cargo_toml_not_found() {
         // Create a temporary directory without a Cargo.toml file
         let temp_dir = tempdir().expect("Failed to create temp dir");
 
-        // This should panic because Cargo.toml does not exist in the temp directory
-        get_root_manifest(temp_dir.path());
+        // This should fail because Cargo.toml does not exist in the temp directory
+        let result = get_root_manifest(temp_dir.path());
+        assert!(matches!(result, Err(Error::MissingManifest(_))));
+    }
+
+    #[test]
+    fn test_read_cargo_toml_of_single_package() {
+        let temp_dir = tempdir().expect("Failed to create temp dir");
+        let mut file =
+            File::create(temp_dir.path().join("Cargo.toml")).expect("Failed to create Cargo.toml");
+        writeln!(file, "[package]\nname = \"single\"\n").expect("Failed to write to Cargo.toml");
+
+        let manifest = get_root_manifest(temp_dir.path()).unwrap().manifest;
+        assert_eq!(manifest.package.unwrap().name, "single");
+        assert!(manifest.workspace.is_none());
+    }
+
+    #[test]
+    fn test_read_cargo_toml_without_workspace_or_package() {
+        let temp_dir = tempdir().expect("Failed to create temp dir");
+        let mut file =
+            File::create(temp_dir.path().join("Cargo.toml")).expect("Failed to create Cargo.toml");
+        writeln!(file, "[dependencies]\nserde = \"1\"\n").expect("Failed to write to Cargo.toml");
+
+        let result = get_root_manifest(temp_dir.path());
+        assert!(matches!(result, Err(Error::NotAWorkspace(_))));
     }
 }
@@ -0,0 +1,305 @@
+use crate::error::{Error, Result};
+use crate::graph::{DependencyKind, EdgeInfo, Graph};
+use crate::types::metadata::{Metadata, MetadataDependency, MetadataPackage};
+use std::collections::HashMap;
+use std::fs;
+use std::io::{stdin, Read};
+use std::path::PathBuf;
+
+/// Reads the output of `cargo metadata --format-version 1` from a file, or from stdin if the source is `-`,
+/// and builds the graph of the workspace from it. Returns the names of the default members as well.
+pub fn get_dependency_graph_from_metadata(source: &str) -> Result<(Graph, Vec<String>)> {
+    let content = if source == "-" {
+        let mut content = String::new();
+        stdin()
+            .read_to_string(&mut content)
+            .map_err(|e| Error::Read {
+                path: PathBuf::from("stdin"),
+                source: e,
+            })?;
+        content
+    } else {
+        fs::read_to_string(source).map_err(|e| Error::Read {
+            path: PathBuf::from(source),
+            source: e,
+        })?
+    };
+
+    let metadata: Metadata = serde_json::from_str(&content).map_err(|error| Error::Metadata {
+        source: source.to_string(),
+        error,
+    })?;
+    Ok((
+        Graph::new_from_metadata(&metadata),
+        default_member_names(&metadata),
+    ))
+}
+
+/// Returns the names of the default members, or of all members if the metadata doesn't list them.
+fn default_member_names(metadata: &Metadata) -> Vec<String> {
+    let ids = metadata
+        .workspace_default_members
+        .as_ref()
+        .unwrap_or(&metadata.workspace_members);
+    metadata
+        .packages
+        .iter()
+        .filter(|package| ids.contains(&package.id))
+        .map(|package| package.name.clone())
+        .collect()
+}
+
+impl Graph {
+    /// Populates the graph with the workspace members of the metadata and their dependencies.
+    /// Packages which are referenced by path, but aren't members of the workspace, are added as external path packages.
+    /// If the metadata contains the resolved graph, only the dependencies which Cargo activated are included.
+    pub fn new_from_metadata(metadata: &Metadata) -> Self {
+        let mut graph = Graph::new();
+        let packages: HashMap<&str, &MetadataPackage> = metadata
+            .packages
+            .iter()
+            .map(|package| (package.id.as_str(), package))
+            .collect();
+
+        let external_path_ids = metadata.packages.iter().filter(|package| {
+            package.source.is_none() && !metadata.workspace_members.contains(&package.id)
+        });
+        for package in external_path_ids.clone() {
+            graph.external_paths.insert(package.name.clone());
+        }
+
+        let node_ids = metadata
+            .workspace_members
+            .iter()
+            .chain(external_path_ids.map(|package| &package.id));
+        for member_id in node_ids {
+            let Some(member) = packages.get(member_id.as_str()) else {
+                continue;
+            };
+            graph.adjacency_list.entry(member.name.clone()).or_default();
+            if !member.features.is_empty() {
+                graph
+                    .features
+                    .insert(member.name.clone(), member.features.clone());
+            }
+
+            let node = metadata
+                .resolve
+                .as_ref()
+                .and_then(|resolve| resolve.nodes.iter().find(|node| node.id == *member_id));
+
+            match node {
+                Some(node) => {
+                    for dep in &node.deps {
+                        let Some(target) = packages.get(dep.pkg.as_str()) else {
+                            continue;
+                        };
+                        for dep_kind in &dep.dep_kinds {
+                            let kind = parse_kind(dep_kind.kind.as_deref());
+                            let declaration = member.dependencies.iter().find(|declared| {
+                                declared.name == target.name
+                                    && parse_kind(declared.kind.as_deref()) == kind
+                                    && declared.target == dep_kind.target
+                            });
+                            let info = match declaration {
+                                Some(declaration) => edge_info(declaration),
+                                None => EdgeInfo {
+                                    kind,
+                                    target: dep_kind.target.clone(),
+                                    ..Default::default()
+                                },
+                            };
+                            graph.add_edge_with_info(&member.name, &target.name, info);
+                        }
+                    }
+                }
+                None => {
+                    for declaration in &member.dependencies {
+                        graph.add_edge_with_info(
+                            &member.name,
+                            &declaration.name,
+                            edge_info(declaration),
+                        );
+                    }
+                }
+            }
+        }
+
+        for dependencies in graph.adjacency_list.values_mut() {
+            dependencies.sort();
+        }
+
+        graph
+    }
+}
+
+fn parse_kind(kind: Option<&str>) -> DependencyKind {
+    match kind {
+        Some("dev") => DependencyKind::Dev,
+        Some("build") => DependencyKind::Build,
+        _ => DependencyKind::Normal,
+    }
+}
+
+fn edge_info(declaration: &MetadataDependency) -> EdgeInfo {
+    EdgeInfo {
+        kind: parse_kind(declaration.kind.as_deref()),
+        alias: declaration.rename.clone(),
+        target: declaration.target.clone(),
+        optional: declaration.optional,
+        features: declaration.features.clone(),
+        no_default_features: !declaration.uses_default_features,
+        version: declaration.req.clone().filter(|req| req != "*"),
+        inherited: false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const METADATA: &str = r#"{
+        "packages": [
+            {
+                "name": "app",
+                "version": "0.1.0",
+                "id": "path+file:///ws/app#0.1.0",
+                "dependencies": [
+                    {"name": "acme-core", "kind": null, "rename": "core", "optional": false,
+                     "features": [], "uses_default_features": true, "target": null},
+                    {"name": "tls", "kind": null, "rename": null, "optional": true,
+                     "features": [], "uses_default_features": true, "target": null},
+                    {"name": "serde", "kind": null, "rename": null, "optional": false,
+                     "features": ["derive"], "uses_default_features": true, "target": null},
+                    {"name": "test-utils", "kind": "dev", "rename": null, "optional": false,
+                     "features": [], "uses_default_features": true, "target": "cfg(unix)"}
+                ],
+                "features": {"secure": ["dep:tls"]}
+            },
+            {"name": "acme-core", "version": "0.1.0", "id": "path+file:///ws/core#0.1.0", "dependencies": [], "features": {}},
+            {"name": "tls", "version": "0.1.0", "id": "path+file:///ws/tls#0.1.0", "dependencies": [], "features": {}},
+            {
+                "name": "test-utils",
+                "version": "0.1.0",
+                "id": "path+file:///ws/test-utils#0.1.0",
+                "dependencies": [
+                    {"name": "app", "kind": null, "rename": null, "optional": false,
+                     "features": [], "uses_default_features": true, "target": null}
+                ],
+                "features": {}
+            },
+            {"name": "serde", "version": "1.0.200", "id": "registry+https://github.com/rust-lang/crates.io-index#serde@1.0.200",
+             "source": "registry+https://github.com/rust-lang/crates.io-index", "dependencies": [], "features": {}}
+        ],
+        "workspace_members": [
+            "path+file:///ws/app#0.1.0",
+            "path+file:///ws/core#0.1.0",
+            "path+file:///ws/tls#0.1.0",
+            "path+file:///ws/test-utils#0.1.0"
+        ],
+        "RESOLVE"
+        "workspace_root": "/ws"
+    }"#;
+
+    const RESOLVE: &str = r#""resolve": {
+            "nodes": [
+                {
+                    "id": "path+file:///ws/app#0.1.0",
+                    "deps": [
+                        {"name": "core", "pkg": "path+file:///ws/core#0.1.0",
+                         "dep_kinds": [{"kind": null, "target": null}]},
+                        {"name": "serde", "pkg": "registry+https://github.com/rust-lang/crates.io-index#serde@1.0.200",
+                         "dep_kinds": [{"kind": null, "target": null}]},
+                        {"name": "test_utils", "pkg": "path+file:///ws/test-utils#0.1.0",
+                         "dep_kinds": [{"kind": "dev", "target": "cfg(unix)"}]}
+                    ]
+                },
+                {"id": "path+file:///ws/core#0.1.0", "deps": []},
+                {"id": "path+file:///ws/tls#0.1.0", "deps": []},
+                {
+                    "id": "path+file:///ws/test-utils#0.1.0",
+                    "deps": [
+                        {"name": "app", "pkg": "path+file:///ws/app#0.1.0",
+                         "dep_kinds": [{"kind": null, "target": null}]}
+                    ]
+                }
+            ]
+        },"#;
+
+    fn parse(resolve: &str) -> Metadata {
+        serde_json::from_str(&METADATA.replace("\"RESOLVE\"", resolve)).unwrap()
+    }
+
+    #[test]
+    fn test_graph_from_resolved_metadata() {
+        let graph = Graph::new_from_metadata(&parse(RESOLVE));
+
+        assert_eq!(graph.get_node_count(), 4);
+        // the optional dependency on `tls` is not activated by the resolved features
+        assert_eq!(
+            graph.adjacency_list["app"],
+            vec!["acme-core", "serde", "test-utils"]
+        );
+        assert_eq!(graph.adjacency_list["test-utils"], vec!["app"]);
+        assert_eq!(graph.get_edge_aliases("app", "acme-core"), vec!["core"]);
+        assert_eq!(
+            graph.get_edge_kind("app", "test-utils"),
+            DependencyKind::Dev
+        );
+        assert_eq!(
+            graph.get_edge_targets("app", "test-utils"),
+            vec!["cfg(unix)"]
+        );
+
+        // external packages are not nodes of the workspace
+        let filtered = graph.filter_dependencies();
+        assert_eq!(
+            filtered.adjacency_list["app"],
+            vec!["acme-core", "test-utils"]
+        );
+    }
+
+    #[test]
+    fn test_graph_from_metadata_without_resolve() {
+        let graph = Graph::new_from_metadata(&parse(""));
+
+        // without the resolved graph, all declared dependencies are included
+        assert_eq!(
+            graph.adjacency_list["app"],
+            vec!["acme-core", "serde", "test-utils", "tls"]
+        );
+        assert!(graph.is_edge_optional("app", "tls"));
+        assert_eq!(graph.get_enabling_features("app", "tls"), vec!["secure"]);
+    }
+
+    #[test]
+    fn test_external_path_packages() {
+        let metadata: Metadata = serde_json::from_str(
+            r#"{
+            "packages": [
+                {"name": "app", "id": "app 0.1.0", "source": null, "dependencies": [
+                    {"name": "shared", "kind": null, "rename": null, "optional": false,
+                     "features": [], "uses_default_features": true, "target": null}
+                ]},
+                {"name": "shared", "id": "shared 0.1.0", "source": null, "dependencies": [
+                    {"name": "serde", "kind": null, "rename": null, "optional": false,
+                     "features": [], "uses_default_features": true, "target": null}
+                ]},
+                {"name": "serde", "id": "serde 1.0.200", "source": "registry+https://github.com/rust-lang/crates.io-index",
+                 "dependencies": []}
+            ],
+            "workspace_members": ["app 0.1.0"],
+            "workspace_default_members": ["app 0.1.0"],
+            "resolve": null
+        }"#,
+        )
+        .unwrap();
+        let graph = Graph::new_from_metadata(&metadata);
+
+        assert_eq!(graph.get_node_count(), 2);
+        assert!(graph.is_external_path("shared"));
+        assert!(!graph.is_external_path("app"));
+        assert_eq!(graph.adjacency_list["shared"], vec!["serde"]);
+        assert_eq!(default_member_names(&metadata), vec!["app"]);
+    }
+}
@@ -1,5 +1,12 @@
+use crate::cycles::{cycle_text, CycleAnalysis};
+use crate::error::{Diagnostic, Error, Result};
 use crate::graph::Graph;
+use crate::layers::{layers, Levels};
+use crate::lockfile::{DuplicateCrate, ExternalWeights, LockedPackage};
 use crate::metrics::CouplingMetric;
+use crate::report::Report;
+use crate::version_drift::VersionDrift;
+use std::collections::BTreeSet;
 use tabled::settings::Style;
 use tabled::{Table, Tabled};
 
@@ -7,6 +14,7 @@ use tabled::{Table, Tabled};
 #[tabled(rename_all = "PascalCase")]
 struct CouplingRow {
     package: String,
+    level: usize,
     fan_in: usize,
     fan_out: usize,
     instability: String,
@@ -20,8 +28,53 @@ struct CountMetrics {
     dependencies: usize,
 }
 
+#[derive(Tabled)]
+#[tabled(rename_all = "PascalCase")]
+struct ExternalWeightRow {
+    package: String,
+    direct: usize,
+    transitive: usize,
+    depth: usize,
+}
+
+#[derive(Tabled)]
+#[tabled(rename_all = "PascalCase")]
+struct ExternalCrateRow {
+    #[tabled(rename = "External Crate")]
+    name: String,
+    version: String,
+    source: String,
+    #[tabled(rename = "Used By")]
+    used_by: String,
+}
+
+#[derive(Tabled)]
+#[tabled(rename_all = "PascalCase")]
+struct DuplicateRow {
+    #[tabled(rename = "Duplicate Crate")]
+    name: String,
+    version: String,
+    #[tabled(rename = "Pulled In By")]
+    chain: String,
+}
+
+#[derive(Tabled)]
+#[tabled(rename_all = "PascalCase")]
+struct DriftRow {
+    #[tabled(rename = "Dependency")]
+    name: String,
+    #[tabled(rename = "Declared By")]
+    member: String,
+    version: String,
+    features: String,
+    #[tabled(rename = "Default Features")]
+    default_features: bool,
+}
+
 pub fn print_counts(graph: &Graph, filtered: &Graph) {
-    let counts = vec![
+    let workspace = filtered.split_external_paths(false);
+    let external_paths = filtered.split_external_paths(true);
+    let mut counts = vec![
         CountMetrics {
             category: "Total",
             packages: graph.get_node_count(),
@@ -29,16 +82,23 @@ pub fn print_counts(graph: &Graph, filtered: &Graph) {
         },
         CountMetrics {
             category: "Workspace",
-            packages: filtered.get_node_count(),
-            dependencies: filtered.get_edge_count(),
+            packages: workspace.get_node_count(),
+            dependencies: workspace.get_edge_count(),
         },
     ];
+    if external_paths.get_node_count() > 0 {
+        counts.push(CountMetrics {
+            category: "External Path",
+            packages: external_paths.get_node_count(),
+            dependencies: external_paths.get_edge_count(),
+        });
+    }
 
     let table = Table::new(counts).with(Style::rounded()).to_string();
     println!("{}", table);
 }
 
-pub fn print_coupling(metrics: CouplingMetric) {
+pub fn print_coupling(metrics: CouplingMetric, levels: &Levels) {
     if metrics.is_empty() {
         println!("No packages found in the graph.");
         return;
@@ -47,6 +107,7 @@ pub fn print_coupling(metrics: CouplingMetric) {
     let rows: Vec<CouplingRow> = metrics
         .into_iter()
         .map(|(package, data)| CouplingRow {
+            level: levels.get(&package).copied().unwrap_or_default(),
             package,
             fan_in: data.fan_in,
             fan_out: data.fan_out,
@@ -58,3 +119,263 @@ pub fn print_coupling(metrics: CouplingMetric) {
 
     println!("{}", table);
 }
+
+#[derive(Tabled)]
+#[tabled(rename_all = "PascalCase")]
+struct LayerRow {
+    level: usize,
+    packages: String,
+}
+
+/// Prints the packages of each layer, starting with the packages without dependencies.
+pub fn print_layers(levels: &Levels) {
+    let rows: Vec<LayerRow> = layers(levels)
+        .into_iter()
+        .enumerate()
+        .map(|(level, packages)| LayerRow {
+            level,
+            packages: packages.join(", "),
+        })
+        .collect();
+    if rows.is_empty() {
+        return;
+    }
+
+    let table = Table::new(rows).with(Style::rounded()).to_string();
+    println!("{}", table);
+}
+
+#[derive(Tabled)]
+#[tabled(rename_all = "PascalCase")]
+struct ComponentRow {
+    component: usize,
+    packages: String,
+}
+
+#[derive(Tabled)]
+#[tabled(rename_all = "PascalCase")]
+struct CycleRow {
+    component: usize,
+    #[tabled(rename = "Circular Dependency")]
+    cycle: String,
+}
+
+#[derive(Tabled)]
+#[tabled(rename_all = "PascalCase")]
+struct FeedbackEdgeRow {
+    #[tabled(rename = "Remove Dependency")]
+    dependency: String,
+    component: usize,
+    cycles: usize,
+}
+
+/// Returns the number of dependencies, e.g. `1 dependency` or `2 dependencies`.
+fn dependency_count(count: usize) -> String {
+    if count == 1 {
+        "1 dependency".to_string()
+    } else {
+        format!("{} dependencies", count)
+    }
+}
+
+pub fn print_cycles(cycles: &CycleAnalysis) {
+    if cycles.is_empty() {
+        return;
+    }
+
+    let rows: Vec<ComponentRow> = cycles
+        .components
+        .iter()
+        .enumerate()
+        .map(|(i, component)| ComponentRow {
+            component: i + 1,
+            packages: component.join(", "),
+        })
+        .collect();
+
+    let table = Table::new(rows).with(Style::rounded()).to_string();
+    println!("{}", table);
+
+    let rows: Vec<CycleRow> = cycles
+        .cycles
+        .iter()
+        .map(|path| CycleRow {
+            component: cycles.component_of(&path[0]).map_or(0, |i| i + 1),
+            cycle: cycle_text(path),
+        })
+        .collect();
+
+    let table = Table::new(rows).with(Style::rounded()).to_string();
+    println!("{}", table);
+
+    if cycles.truncated {
+        println!(
+            "⚠️  Only the first {} circular dependencies are listed. Use --cycle-limit to list more.",
+            cycles.cycles.len()
+        );
+    }
+
+    let rows: Vec<FeedbackEdgeRow> = cycles
+        .feedback_arc_set
+        .iter()
+        .map(|edge| FeedbackEdgeRow {
+            dependency: format!("{} → {}", edge.from, edge.to),
+            component: edge.component + 1,
+            cycles: edge.cycles,
+        })
+        .collect();
+
+    let table = Table::new(rows).with(Style::rounded()).to_string();
+    println!("{}", table);
+    println!(
+        "💡 Removing the {} listed above makes the workspace acyclic.",
+        dependency_count(cycles.feedback_arc_set.len())
+    );
+}
+
+pub fn print_external_dependencies(
+    weights: ExternalWeights,
+    externals: Vec<(&LockedPackage, BTreeSet<&str>)>,
+) {
+    if weights.is_empty() {
+        println!("No workspace members found in the lockfile.");
+        return;
+    }
+
+    let mut rows: Vec<ExternalWeightRow> = weights
+        .into_iter()
+        .map(|(package, weight)| ExternalWeightRow {
+            package,
+            direct: weight.direct,
+            transitive: weight.transitive,
+            depth: weight.depth,
+        })
+        .collect();
+    rows.sort_by(|a, b| {
+        b.transitive
+            .cmp(&a.transitive)
+            .then(a.package.cmp(&b.package))
+    });
+
+    let table = Table::new(rows).with(Style::rounded()).to_string();
+    println!("{}", table);
+
+    let rows: Vec<ExternalCrateRow> = externals
+        .into_iter()
+        .map(|(package, used_by)| ExternalCrateRow {
+            name: package.name.clone(),
+            version: package.version.clone(),
+            source: package.origin().to_string(),
+            used_by: used_by.into_iter().collect::<Vec<_>>().join(", "),
+        })
+        .collect();
+
+    let table = Table::new(rows).with(Style::rounded()).to_string();
+    println!("{}", table);
+}
+
+pub fn print_duplicates(duplicates: Vec<DuplicateCrate>) {
+    if duplicates.is_empty() {
+        println!("No duplicate versions of external crates found.");
+        return;
+    }
+
+    let mut rows = Vec::new();
+    for duplicate in duplicates {
+        for version in duplicate.versions {
+            if version.chains.is_empty() {
+                rows.push(DuplicateRow {
+                    name: duplicate.name.clone(),
+                    version: version.version.clone(),
+                    chain: "-".to_string(),
+                });
+            }
+            for chain in version.chains {
+                rows.push(DuplicateRow {
+                    name: duplicate.name.clone(),
+                    version: version.version.clone(),
+                    chain: chain.join(" → "),
+                });
+            }
+        }
+    }
+
+    let table = Table::new(rows).with(Style::rounded()).to_string();
+    println!("{}", table);
+}
+
+pub fn print_version_drift(drift: Vec<VersionDrift>) {
+    if drift.is_empty() {
+        println!("No version drift of shared external dependencies found.");
+        return;
+    }
+
+    let hoistable: Vec<String> = drift
+        .iter()
+        .filter(|d| d.is_hoistable())
+        .map(|d| d.name.clone())
+        .collect();
+
+    let rows: Vec<DriftRow> = drift
+        .into_iter()
+        .flat_map(|d| {
+            let name = d.name;
+            d.declarations.into_iter().map(move |declaration| DriftRow {
+                name: name.clone(),
+                member: declaration.member,
+                version: match (declaration.inherited, declaration.version) {
+                    (true, Some(version)) => format!("{} (workspace)", version),
+                    (true, None) => "workspace".to_string(),
+                    (false, version) => version.unwrap_or_else(|| "-".to_string()),
+                },
+                features: declaration.features.join(", "),
+                default_features: declaration.default_features,
+            })
+        })
+        .collect();
+
+    let table = Table::new(rows).with(Style::rounded()).to_string();
+    println!("{}", table);
+
+    if !hoistable.is_empty() {
+        println!(
+            "💡 Consider declaring {} once in [workspace.dependencies] and inheriting it with `workspace = true`.",
+            hoistable.join(", ")
+        );
+    }
+}
+
+#[derive(Tabled)]
+#[tabled(rename_all = "PascalCase")]
+struct SkippedManifestRow {
+    #[tabled(rename = "Skipped Manifest")]
+    path: String,
+    reason: String,
+}
+
+pub fn print_skipped_manifests(diagnostics: &[Diagnostic]) {
+    if diagnostics.is_empty() {
+        return;
+    }
+
+    let rows: Vec<SkippedManifestRow> = diagnostics
+        .iter()
+        .map(|diagnostic| SkippedManifestRow {
+            path: diagnostic.path.display().to_string(),
+            reason: diagnostic.reason.clone(),
+        })
+        .collect();
+
+    let table = Table::new(rows).with(Style::rounded()).to_string();
+    println!("{}", table);
+    println!(
+        "⚠️  {} manifest(s) were skipped. Use --strict to fail instead.",
+        diagnostics.len()
+    );
+}
+
+pub fn print_json_report(report: &Report) -> Result<()> {
+    let json = serde_json::to_string_pretty(report).map_err(Error::Serialize)?;
+    println!("{}", json);
+    Ok(())
+}
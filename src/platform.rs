@@ -0,0 +1,331 @@
+use std::collections::HashSet;
+
+/// Describes the platform the graph is evaluated for.
+/// Target-specific dependencies like `[target.'cfg(unix)'.dependencies]` are only kept if they apply to it.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Platform {
+    triple: Option<String>,
+    cfgs: HashSet<Cfg>,
+}
+
+/// A single configuration option, either a name like `unix` or a key value pair like `target_os = "linux"`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Cfg {
+    name: String,
+    value: Option<String>,
+}
+
+impl Platform {
+    /// Creates a platform from a target triple like `x86_64-unknown-linux-gnu`.
+    /// The cfg values are derived from the components of the triple, which covers the common targets.
+    pub fn from_triple(triple: &str) -> Self {
+        let mut platform = Platform {
+            triple: Some(triple.to_string()),
+            cfgs: HashSet::new(),
+        };
+
+        let parts: Vec<&str> = triple.split('-').collect();
+        let raw_arch = parts[0];
+        let arch = normalize_arch(raw_arch);
+        let os = find_os(&parts[1..]);
+        let env = find_env(&parts[1..]);
+
+        let family = if os == "windows" {
+            Some("windows")
+        } else if arch.starts_with("wasm") {
+            Some("wasm")
+        } else if os == "none" || os == "unknown" {
+            None
+        } else {
+            Some("unix")
+        };
+
+        let pointer_width = if raw_arch.contains("64") || raw_arch == "s390x" {
+            "64"
+        } else {
+            "32"
+        };
+        let big_endian = match arch.as_str() {
+            "powerpc" | "powerpc64" => !raw_arch.ends_with("le"),
+            "s390x" | "sparc64" | "mips" | "mips64" => true,
+            _ => false,
+        };
+
+        platform.insert("target_arch", Some(&arch));
+        platform.insert("target_os", Some(&os));
+        platform.insert("target_env", Some(&env));
+        platform.insert("target_pointer_width", Some(pointer_width));
+        platform.insert(
+            "target_endian",
+            Some(if big_endian { "big" } else { "little" }),
+        );
+        if parts.len() > 2 {
+            platform.insert("target_vendor", Some(parts[1]));
+        }
+        if let Some(family) = family {
+            platform.insert("target_family", Some(family));
+            if family != "wasm" {
+                platform.insert(family, None);
+            }
+        }
+
+        platform
+    }
+
+    /// Adds a cfg given as `name` or `name=value`, e.g. `unix` or `target_os="linux"`.
+    pub fn add_cfg(&mut self, cfg: &str) {
+        match cfg.split_once('=') {
+            Some((name, value)) => {
+                let value = value.trim().trim_matches('"');
+                self.insert(name.trim(), Some(value));
+            }
+            None => self.insert(cfg.trim(), None),
+        }
+    }
+
+    fn insert(&mut self, name: &str, value: Option<&str>) {
+        self.cfgs.insert(Cfg {
+            name: name.to_string(),
+            value: value.map(|v| v.to_string()),
+        });
+    }
+
+    /// Checks whether a platform specification of a `[target.<spec>]` table applies to this platform.
+    /// The specification is either a `cfg(...)` expression or a target triple.
+    /// Expressions which cannot be parsed never apply.
+    pub fn matches(&self, spec: &str) -> bool {
+        let spec = spec.trim();
+        match spec.strip_prefix("cfg(").and_then(|s| s.strip_suffix(')')) {
+            Some(expression) => CfgParser::new(expression)
+                .parse()
+                .map(|expression| expression.evaluate(self))
+                .unwrap_or(false),
+            None => self.triple.as_deref() == Some(spec),
+        }
+    }
+}
+
+fn normalize_arch(arch: &str) -> String {
+    match arch {
+        "i386" | "i586" | "i686" => "x86".to_string(),
+        "arm64" => "aarch64".to_string(),
+        "powerpc64le" => "powerpc64".to_string(),
+        a if a.starts_with("armv") || a.starts_with("thumbv") => "arm".to_string(),
+        a if a.starts_with("riscv64") => "riscv64".to_string(),
+        a if a.starts_with("riscv32") => "riscv32".to_string(),
+        a => a.to_string(),
+    }
+}
+
+fn find_os(parts: &[&str]) -> String {
+    const KNOWN: [&str; 16] = [
+        "android",
+        "linux",
+        "windows",
+        "darwin",
+        "macos",
+        "ios",
+        "freebsd",
+        "netbsd",
+        "openbsd",
+        "dragonfly",
+        "solaris",
+        "illumos",
+        "fuchsia",
+        "redox",
+        "wasi",
+        "none",
+    ];
+
+    // Android triples also contain `linux`, so the more specific name is checked first
+    KNOWN
+        .iter()
+        .find(|os| parts.iter().any(|part| part.starts_with(*os)))
+        .map(|os| if *os == "darwin" { "macos" } else { os })
+        .unwrap_or("unknown")
+        .to_string()
+}
+
+fn find_env(parts: &[&str]) -> String {
+    let last = parts.last().copied().unwrap_or_default();
+    ["gnu", "musl", "msvc", "sgx", "uclibc"]
+        .iter()
+        .find(|env| last.starts_with(*env))
+        .map(|env| env.to_string())
+        .unwrap_or_default()
+}
+
+/// A parsed `cfg(...)` expression.
+#[derive(Debug, PartialEq, Eq)]
+enum CfgExpression {
+    Option(Cfg),
+    All(Vec<CfgExpression>),
+    Any(Vec<CfgExpression>),
+    Not(Box<CfgExpression>),
+}
+
+impl CfgExpression {
+    fn evaluate(&self, platform: &Platform) -> bool {
+        match self {
+            CfgExpression::Option(cfg) => platform.cfgs.contains(cfg),
+            CfgExpression::All(expressions) => expressions.iter().all(|e| e.evaluate(platform)),
+            CfgExpression::Any(expressions) => expressions.iter().any(|e| e.evaluate(platform)),
+            CfgExpression::Not(expression) => !expression.evaluate(platform),
+        }
+    }
+}
+
+/// A small recursive descent parser for the content of `cfg(...)`.
+struct CfgParser<'a> {
+    rest: &'a str,
+}
+
+impl<'a> CfgParser<'a> {
+    fn new(input: &'a str) -> Self {
+        CfgParser { rest: input }
+    }
+
+    fn parse(mut self) -> Option<CfgExpression> {
+        let expression = self.expression()?;
+        self.rest.trim().is_empty().then_some(expression)
+    }
+
+    fn expression(&mut self) -> Option<CfgExpression> {
+        let name = self.identifier()?;
+
+        match name {
+            "all" | "any" | "not" => {
+                self.expect('(')?;
+                let mut expressions = Vec::new();
+                while !self.peek(')') {
+                    expressions.push(self.expression()?);
+                    if !self.peek(')') {
+                        self.expect(',')?;
+                    }
+                }
+                self.expect(')')?;
+
+                match name {
+                    "all" => Some(CfgExpression::All(expressions)),
+                    "any" => Some(CfgExpression::Any(expressions)),
+                    _ if expressions.len() == 1 => {
+                        Some(CfgExpression::Not(Box::new(expressions.remove(0))))
+                    }
+                    _ => None,
+                }
+            }
+            _ => {
+                let value = if self.peek('=') {
+                    self.expect('=')?;
+                    Some(self.string()?)
+                } else {
+                    None
+                };
+                Some(CfgExpression::Option(Cfg {
+                    name: name.to_string(),
+                    value,
+                }))
+            }
+        }
+    }
+
+    fn identifier(&mut self) -> Option<&'a str> {
+        self.rest = self.rest.trim_start();
+        let end = self
+            .rest
+            .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .unwrap_or(self.rest.len());
+        if end == 0 {
+            return None;
+        }
+        let (identifier, rest) = self.rest.split_at(end);
+        self.rest = rest;
+        Some(identifier)
+    }
+
+    fn string(&mut self) -> Option<String> {
+        self.expect('"')?;
+        let end = self.rest.find('"')?;
+        let value = self.rest[..end].to_string();
+        self.rest = &self.rest[end + 1..];
+        Some(value)
+    }
+
+    fn peek(&mut self, c: char) -> bool {
+        self.rest = self.rest.trim_start();
+        self.rest.starts_with(c)
+    }
+
+    fn expect(&mut self, c: char) -> Option<()> {
+        if self.peek(c) {
+            self.rest = &self.rest[c.len_utf8()..];
+            Some(())
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_linux_triple() {
+        let linux = Platform::from_triple("x86_64-unknown-linux-gnu");
+        assert!(linux.matches("cfg(unix)"));
+        assert!(linux.matches("cfg(target_os = \"linux\")"));
+        assert!(linux.matches("cfg(all(unix, target_pointer_width = \"64\"))"));
+        assert!(linux.matches("cfg(not(windows))"));
+        assert!(linux.matches("x86_64-unknown-linux-gnu"));
+        assert!(!linux.matches("cfg(windows)"));
+        assert!(!linux.matches("x86_64-pc-windows-msvc"));
+    }
+
+    #[test]
+    fn test_windows_triple() {
+        let windows = Platform::from_triple("x86_64-pc-windows-msvc");
+        assert!(windows.matches("cfg(windows)"));
+        assert!(windows.matches("cfg(target_env = \"msvc\")"));
+        assert!(windows.matches("cfg(any(target_os = \"macos\", windows))"));
+        assert!(windows.matches("x86_64-pc-windows-msvc"));
+        assert!(!windows.matches("cfg(unix)"));
+    }
+
+    #[test]
+    fn test_other_triples() {
+        let mac = Platform::from_triple("aarch64-apple-darwin");
+        assert!(mac.matches("cfg(target_os = \"macos\")"));
+        assert!(mac.matches("cfg(target_arch = \"aarch64\")"));
+        assert!(mac.matches("cfg(target_vendor = \"apple\")"));
+
+        let android = Platform::from_triple("aarch64-linux-android");
+        assert!(android.matches("cfg(target_os = \"android\")"));
+        assert!(android.matches("cfg(unix)"));
+
+        let wasm = Platform::from_triple("wasm32-unknown-unknown");
+        assert!(wasm.matches("cfg(target_family = \"wasm\")"));
+        assert!(wasm.matches("cfg(target_pointer_width = \"32\")"));
+        assert!(!wasm.matches("cfg(unix)"));
+    }
+
+    #[test]
+    fn test_custom_cfgs() {
+        let mut platform = Platform::default();
+        platform.add_cfg("unix");
+        platform.add_cfg("feature=\"tls\"");
+        assert!(platform.matches("cfg(unix)"));
+        assert!(platform.matches("cfg(feature = \"tls\")"));
+        assert!(!platform.matches("cfg(windows)"));
+        assert!(!platform.matches("x86_64-unknown-linux-gnu"));
+    }
+
+    #[test]
+    fn test_invalid_expressions() {
+        let linux = Platform::from_triple("x86_64-unknown-linux-gnu");
+        assert!(!linux.matches("cfg(unix"));
+        assert!(!linux.matches("cfg(all(unix,)"));
+        assert!(!linux.matches("cfg(not(unix, windows))"));
+        assert!(!linux.matches("cfg(target_os = linux)"));
+    }
+}
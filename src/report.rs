@@ -0,0 +1,376 @@
+use crate::cycles::{CycleAnalysis, FeedbackEdge};
+use crate::error::Diagnostic;
+use crate::graph::{DependencyKind, Graph};
+use crate::layers::{layers, Levels};
+use crate::lockfile::{DuplicateCrate, ExternalWeights, LockedPackage};
+use crate::metrics::CouplingMetric;
+use crate::version_drift::{Declaration, VersionDrift};
+use serde::Serialize;
+use std::collections::BTreeSet;
+
+/// The version of the JSON schema. It is increased whenever existing fields change or are removed.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// The machine-readable report of the whole analysis.
+/// Optional sections are omitted if the corresponding analysis was not requested.
+#[derive(Serialize, Debug)]
+pub struct Report {
+    pub schema_version: u32,
+    pub nodes: Vec<NodeEntry>,
+    pub edges: Vec<EdgeEntry>,
+    pub counts: Counts,
+    pub coupling: Vec<CouplingEntry>,
+    /// The packages of each architectural layer, starting with the packages without dependencies.
+    pub layers: Vec<Vec<String>>,
+    pub cycles: Cycles,
+    /// The manifests which were skipped and left out of the analysis, each with the reason.
+    pub skipped_manifests: Vec<Diagnostic>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub drift: Option<Vec<DriftEntry>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external: Option<External>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duplicates: Option<Vec<DuplicateCrate>>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct NodeEntry {
+    pub name: String,
+    pub category: NodeCategory,
+    /// The packages which are collapsed into the node, if the graph is condensed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub members: Option<Vec<String>>,
+}
+
+#[derive(Serialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum NodeCategory {
+    /// A member of the workspace.
+    Workspace,
+    /// A package outside the workspace, which is reached via a path dependency.
+    ExternalPath,
+    /// A strongly connected component, which is collapsed into a single node.
+    Component,
+}
+
+/// An edge between two workspace members, combining all declarations of the dependency.
+#[derive(Serialize, Debug)]
+pub struct EdgeEntry {
+    pub from: String,
+    pub to: String,
+    pub kinds: Vec<DependencyKind>,
+    pub aliases: Vec<String>,
+    /// The platforms the dependency is restricted to, empty if it applies to all of them.
+    pub targets: Vec<String>,
+    pub optional: bool,
+    /// The features enabling the dependency, if it is optional.
+    pub enabling_features: Vec<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct Counts {
+    pub total: Count,
+    pub workspace: Count,
+    pub external_path: Count,
+}
+
+#[derive(Serialize, Debug)]
+pub struct Count {
+    pub packages: usize,
+    pub dependencies: usize,
+}
+
+#[derive(Serialize, Debug)]
+pub struct CouplingEntry {
+    pub package: String,
+    /// The length of the longest path to a package without dependencies.
+    pub level: usize,
+    pub fan_in: usize,
+    pub fan_out: usize,
+    pub instability: f32,
+}
+
+#[derive(Serialize, Debug)]
+pub struct Cycles {
+    /// The strongly connected components, which consist of more than one package or of a package depending on itself.
+    pub components: Vec<Vec<String>>,
+    /// The elementary cycles, each given by its packages. The last package depends on the first one.
+    pub paths: Vec<Vec<String>>,
+    /// Whether more elementary cycles than the limit exist, which are not listed.
+    pub truncated: bool,
+    /// The dependencies whose removal makes the workspace acyclic, ranked by the number of cycles they are part of.
+    pub feedback_arc_set: Vec<FeedbackEdge>,
+    /// The edges which are part of a circular dependency.
+    pub edges: Vec<CycleEdge>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct CycleEdge {
+    pub from: String,
+    pub to: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct DriftEntry {
+    pub name: String,
+    pub declarations: Vec<Declaration>,
+    /// Whether the dependency should be declared once in `[workspace.dependencies]`.
+    pub suggest_hoisting: bool,
+}
+
+#[derive(Serialize, Debug)]
+pub struct External {
+    pub members: Vec<ExternalWeightEntry>,
+    pub crates: Vec<ExternalCrateEntry>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ExternalWeightEntry {
+    pub package: String,
+    pub direct: usize,
+    pub transitive: usize,
+    pub depth: usize,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ExternalCrateEntry {
+    pub name: String,
+    pub version: String,
+    pub source: Option<String>,
+    pub used_by: Vec<String>,
+}
+
+impl Report {
+    /// Creates the report of the workspace graph. The optional sections are left empty.
+    pub fn new(
+        graph: &Graph,
+        filtered: &Graph,
+        metrics: CouplingMetric,
+        levels: &Levels,
+        cycles: &CycleAnalysis,
+    ) -> Self {
+        let mut names: Vec<&String> = filtered.adjacency_list.keys().collect();
+        names.sort();
+
+        let nodes = names
+            .iter()
+            .map(|name| NodeEntry {
+                name: name.to_string(),
+                category: if filtered.get_group_members(name).is_some() {
+                    NodeCategory::Component
+                } else if filtered.is_external_path(name) {
+                    NodeCategory::ExternalPath
+                } else {
+                    NodeCategory::Workspace
+                },
+                members: filtered
+                    .get_group_members(name)
+                    .map(|members| members.to_vec()),
+            })
+            .collect();
+
+        let mut edges = Vec::new();
+        for from in &names {
+            let mut dependencies = filtered.adjacency_list[*from].clone();
+            dependencies.sort();
+            dependencies.dedup();
+            for to in dependencies {
+                let mut kinds: Vec<DependencyKind> = filtered
+                    .get_edge_infos(from, &to)
+                    .iter()
+                    .map(|info| info.kind)
+                    .collect();
+                kinds.sort();
+                kinds.dedup();
+                edges.push(EdgeEntry {
+                    from: from.to_string(),
+                    kinds,
+                    aliases: to_strings(filtered.get_edge_aliases(from, &to)),
+                    targets: to_strings(filtered.get_edge_targets(from, &to)),
+                    optional: filtered.is_edge_optional(from, &to),
+                    enabling_features: filtered.get_enabling_features(from, &to),
+                    to,
+                });
+            }
+        }
+
+        let mut coupling: Vec<CouplingEntry> = metrics
+            .into_iter()
+            .map(|(package, data)| CouplingEntry {
+                level: levels.get(&package).copied().unwrap_or_default(),
+                package,
+                fan_in: data.fan_in,
+                fan_out: data.fan_out,
+                instability: data.instability,
+            })
+            .collect();
+        coupling.sort_by(|a, b| a.package.cmp(&b.package));
+
+        let cycle_edges = cycles
+            .edges
+            .iter()
+            .map(|(from, to)| CycleEdge {
+                from: from.clone(),
+                to: to.clone(),
+            })
+            .collect();
+
+        Report {
+            schema_version: SCHEMA_VERSION,
+            nodes,
+            edges,
+            counts: Counts {
+                total: Count {
+                    packages: graph.get_node_count(),
+                    dependencies: graph.get_edge_count(),
+                },
+                workspace: count(&filtered.split_external_paths(false)),
+                external_path: count(&filtered.split_external_paths(true)),
+            },
+            coupling,
+            layers: layers(levels),
+            cycles: Cycles {
+                components: cycles.components.clone(),
+                paths: cycles.cycles.clone(),
+                truncated: cycles.truncated,
+                feedback_arc_set: cycles.feedback_arc_set.clone(),
+                edges: cycle_edges,
+            },
+            skipped_manifests: Vec::new(),
+            drift: None,
+            external: None,
+            duplicates: None,
+        }
+    }
+
+    pub fn with_skipped_manifests(mut self, diagnostics: Vec<Diagnostic>) -> Self {
+        self.skipped_manifests = diagnostics;
+        self
+    }
+
+    pub fn with_drift(mut self, drift: Vec<VersionDrift>) -> Self {
+        self.drift = Some(
+            drift
+                .into_iter()
+                .map(|d| DriftEntry {
+                    suggest_hoisting: d.is_hoistable(),
+                    name: d.name,
+                    declarations: d.declarations,
+                })
+                .collect(),
+        );
+        self
+    }
+
+    pub fn with_external(
+        mut self,
+        weights: ExternalWeights,
+        externals: Vec<(&LockedPackage, BTreeSet<&str>)>,
+    ) -> Self {
+        let mut members: Vec<ExternalWeightEntry> = weights
+            .into_iter()
+            .map(|(package, weight)| ExternalWeightEntry {
+                package,
+                direct: weight.direct,
+                transitive: weight.transitive,
+                depth: weight.depth,
+            })
+            .collect();
+        members.sort_by(|a, b| a.package.cmp(&b.package));
+
+        let crates = externals
+            .into_iter()
+            .map(|(package, used_by)| ExternalCrateEntry {
+                name: package.name.clone(),
+                version: package.version.clone(),
+                source: package.source.clone(),
+                used_by: to_strings(used_by.into_iter().collect()),
+            })
+            .collect();
+
+        self.external = Some(External { members, crates });
+        self
+    }
+
+    pub fn with_duplicates(mut self, duplicates: Vec<DuplicateCrate>) -> Self {
+        self.duplicates = Some(duplicates);
+        self
+    }
+}
+
+fn count(graph: &Graph) -> Count {
+    Count {
+        packages: graph.get_node_count(),
+        dependencies: graph.get_edge_count(),
+    }
+}
+
+fn to_strings(values: Vec<&str>) -> Vec<String> {
+    values.into_iter().map(|v| v.to_string()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cycles::DEFAULT_CYCLE_LIMIT;
+    use crate::graph::EdgeInfo;
+
+    #[test]
+    fn test_report_schema() {
+        let mut graph = Graph::new();
+        graph.add_edge("a", "b");
+        graph.add_edge_with_info(
+            "b",
+            "a",
+            EdgeInfo {
+                kind: DependencyKind::Dev,
+                ..Default::default()
+            },
+        );
+        graph.add_edge("b", "serde");
+        let filtered = graph.filter_dependencies();
+        let cycles = filtered.analyze_cycles(DEFAULT_CYCLE_LIMIT);
+
+        let levels = filtered.calculate_levels(&cycles);
+
+        let report = Report::new(
+            &graph,
+            &filtered,
+            filtered.calculate_coupling(),
+            &levels,
+            &cycles,
+        );
+        let json = serde_json::to_value(&report).unwrap();
+
+        assert_eq!(json["schema_version"], 1);
+        assert_eq!(
+            json["nodes"],
+            serde_json::json!([
+                {"name": "a", "category": "workspace"},
+                {"name": "b", "category": "workspace"}
+            ])
+        );
+        assert_eq!(json["edges"][1]["from"], "b");
+        assert_eq!(json["edges"][1]["kinds"], serde_json::json!(["dev"]));
+        assert_eq!(json["counts"]["total"]["dependencies"], 3);
+        assert_eq!(json["counts"]["workspace"]["dependencies"], 2);
+        assert_eq!(json["counts"]["external_path"]["packages"], 0);
+        assert_eq!(json["coupling"][0]["package"], "a");
+        assert_eq!(json["coupling"][0]["level"], 0);
+        assert_eq!(json["layers"], serde_json::json!([["a", "b"]]));
+        assert_eq!(json["cycles"]["edges"].as_array().unwrap().len(), 2);
+        assert_eq!(
+            json["cycles"]["components"],
+            serde_json::json!([["a", "b"]])
+        );
+        assert_eq!(json["cycles"]["paths"], serde_json::json!([["a", "b"]]));
+        assert_eq!(json["cycles"]["truncated"], false);
+        assert_eq!(
+            json["cycles"]["feedback_arc_set"],
+            serde_json::json!([{"from": "a", "to": "b", "component": 0, "cycles": 1}])
+        );
+        assert_eq!(json["skipped_manifests"], serde_json::json!([]));
+        // sections which were not requested are omitted
+        assert!(json.get("drift").is_none());
+    }
+}
@@ -2,7 +2,7 @@ use serde::Deserialize;
 use std::collections::HashMap;
 
 /// Both, the root and the nested manifest must have a `name` attribute.
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, Default)]
 pub struct Package {
     pub name: String,
 }
@@ -12,13 +12,75 @@ pub struct Package {
 /// The parts which are inspected current are in both cases the same.
 #[derive(Deserialize, Debug)]
 #[serde(untagged)]
-#[allow(dead_code)] // may be used later
 pub enum DependencyInfo {
     Simple(Version),
-    Detailed {
-        path: Option<String>,
-        workspace: Option<bool>,
-    },
+    Detailed(DetailedDependency),
+}
+
+/// A dependency declared as a table, e.g. `foo = { path = "../foo", optional = true }`.
+#[derive(Deserialize, Debug, Default)]
+pub struct DetailedDependency {
+    pub version: Option<String>,
+    pub path: Option<String>,
+    pub workspace: Option<bool>,
+    /// The actual name of the package, if the dependency is renamed.
+    pub package: Option<String>,
+    pub optional: Option<bool>,
+    /// The features which are enabled on the dependency.
+    pub features: Option<Vec<String>>,
+    #[serde(rename = "default-features", alias = "default_features")]
+    pub default_features: Option<bool>,
+}
+
+impl DependencyInfo {
+    fn details(&self) -> Option<&DetailedDependency> {
+        match self {
+            DependencyInfo::Detailed(details) => Some(details),
+            DependencyInfo::Simple(_) => None,
+        }
+    }
+
+    /// Returns the version requirement, if there is one.
+    pub fn version(&self) -> Option<&str> {
+        match self {
+            DependencyInfo::Simple(version) => Some(version),
+            DependencyInfo::Detailed(details) => details.version.as_deref(),
+        }
+    }
+
+    /// Returns the path of a path dependency, relative to the declaring manifest.
+    pub fn path(&self) -> Option<&str> {
+        self.details()?.path.as_deref()
+    }
+
+    /// Returns the actual package name, if the dependency is renamed via the `package` key.
+    pub fn renamed_package(&self) -> Option<&str> {
+        self.details()?.package.as_deref()
+    }
+
+    /// Whether the dependency is inherited from `[workspace.dependencies]`.
+    pub fn is_inherited(&self) -> bool {
+        self.details()
+            .is_some_and(|details| details.workspace == Some(true))
+    }
+
+    pub fn is_optional(&self) -> bool {
+        self.details()
+            .is_some_and(|details| details.optional == Some(true))
+    }
+
+    /// Returns the features which are enabled on the dependency.
+    pub fn features(&self) -> &[String] {
+        self.details()
+            .and_then(|details| details.features.as_deref())
+            .unwrap_or_default()
+    }
+
+    /// Whether the default features of the dependency are explicitly disabled.
+    pub fn is_without_default_features(&self) -> bool {
+        self.details()
+            .is_some_and(|details| details.default_features == Some(false))
+    }
 }
 
 pub type Dependencies = HashMap<Name, DependencyInfo>;
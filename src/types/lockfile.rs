@@ -0,0 +1,20 @@
+use serde::Deserialize;
+
+/// The `Cargo.lock` file of a workspace.
+#[derive(Deserialize, Debug)]
+pub struct Lockfile {
+    #[serde(default, rename = "package")]
+    pub packages: Vec<LockfilePackage>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct LockfilePackage {
+    pub name: String,
+    pub version: String,
+    /// Is missing for workspace members and other path dependencies.
+    pub source: Option<String>,
+    /// Each entry is given as `name`, `name version` or `name version (source)`,
+    /// depending on what is needed to identify the package.
+    #[serde(default)]
+    pub dependencies: Vec<String>,
+}
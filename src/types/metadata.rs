@@ -0,0 +1,76 @@
+use crate::types::nested::Features;
+use serde::Deserialize;
+
+/// The output of `cargo metadata --format-version 1`.
+/// Only the parts which are needed to build the graph are deserialized.
+#[derive(Deserialize, Debug)]
+pub struct Metadata {
+    pub packages: Vec<MetadataPackage>,
+    pub workspace_members: Vec<PackageId>,
+    /// Is missing in the output of Cargo versions before 1.71.
+    pub workspace_default_members: Option<Vec<PackageId>>,
+    /// Is missing if the metadata was created with `--no-deps`.
+    pub resolve: Option<Resolve>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct MetadataPackage {
+    pub name: String,
+    pub id: PackageId,
+    /// Is missing for packages which are referenced by path.
+    pub source: Option<String>,
+    pub dependencies: Vec<MetadataDependency>,
+    #[serde(default)]
+    pub features: Features,
+}
+
+/// A dependency as it is declared in the manifest, with workspace inheritance already applied.
+#[derive(Deserialize, Debug)]
+pub struct MetadataDependency {
+    /// The name of the package, even if the dependency is renamed.
+    pub name: String,
+    /// `null` for normal dependencies, otherwise `dev` or `build`.
+    pub kind: Option<String>,
+    pub rename: Option<String>,
+    /// The version requirement, `*` if there is none.
+    pub req: Option<String>,
+    #[serde(default)]
+    pub optional: bool,
+    #[serde(default)]
+    pub features: Vec<String>,
+    #[serde(default = "enabled")]
+    pub uses_default_features: bool,
+    pub target: Option<String>,
+}
+
+/// The resolved dependency graph, which only contains the dependencies activated by the resolved features.
+#[derive(Deserialize, Debug)]
+pub struct Resolve {
+    pub nodes: Vec<ResolveNode>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ResolveNode {
+    pub id: PackageId,
+    #[serde(default)]
+    pub deps: Vec<NodeDependency>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct NodeDependency {
+    pub pkg: PackageId,
+    #[serde(default)]
+    pub dep_kinds: Vec<DependencyKindInfo>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct DependencyKindInfo {
+    pub kind: Option<String>,
+    pub target: Option<String>,
+}
+
+pub type PackageId = String;
+
+fn enabled() -> bool {
+    true
+}
@@ -1,3 +1,5 @@
 pub mod commons;
+pub mod lockfile;
+pub mod metadata;
 pub mod nested;
 pub mod root;
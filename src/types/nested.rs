@@ -1,17 +1,37 @@
 use crate::types::commons::{Dependencies, Package};
 use serde::Deserialize;
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 /// The Cargo.toml file aka. the manifest from a package.
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, Default)]
 pub struct Manifest {
     pub package: Package,
     pub dependencies: Option<Dependencies>,
+    #[serde(rename = "dev-dependencies", alias = "dev_dependencies")]
+    pub dev_dependencies: Option<Dependencies>,
+    #[serde(rename = "build-dependencies", alias = "build_dependencies")]
+    pub build_dependencies: Option<Dependencies>,
+    /// Platform specific dependencies, keyed by a `cfg(...)` expression or a target triple.
+    pub target: Option<HashMap<String, PlatformDependencies>>,
+    pub features: Option<Features>,
+}
+
+/// The `[features]` table, mapping each feature to the features and dependencies it enables.
+pub type Features = HashMap<String, Vec<String>>;
+
+/// The dependency tables of a `[target.<spec>]` table.
+#[derive(Deserialize, Debug, Default)]
+pub struct PlatformDependencies {
+    pub dependencies: Option<Dependencies>,
+    #[serde(rename = "dev-dependencies", alias = "dev_dependencies")]
+    pub dev_dependencies: Option<Dependencies>,
+    #[serde(rename = "build-dependencies", alias = "build_dependencies")]
+    pub build_dependencies: Option<Dependencies>,
 }
 
 #[derive(Debug)]
 pub struct ManifestFinding {
-    #[allow(dead_code)] // may be used later
     // the location on disk
     pub path: PathBuf,
     pub manifest: Manifest,
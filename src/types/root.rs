@@ -1,4 +1,4 @@
-use crate::types::commons::Dependencies;
+use crate::types::commons::{Dependencies, Package};
 use serde::Deserialize;
 use std::path::PathBuf;
 
@@ -8,18 +8,30 @@ pub struct CargoRootManifestFinding {
     pub manifest: CargoRootManifest,
 }
 
-/// The "virtual" manifest
+/// The root manifest, which defines a workspace, a package or both.
+/// The dependencies of a root package are read from its full manifest, like those of every other member.
 #[derive(Deserialize, Debug)]
 pub struct CargoRootManifest {
+    pub package: Option<Package>,
     pub workspace: Option<Workspace>,
-    #[allow(dead_code)] // may be used later
-    pub dependencies: Option<Dependencies>,
+}
+
+impl CargoRootManifest {
+    /// Returns the dependencies which members can inherit via `workspace = true`.
+    pub fn workspace_dependencies(&self) -> Option<&Dependencies> {
+        self.workspace
+            .as_ref()
+            .and_then(|workspace| workspace.dependencies.as_ref())
+    }
 }
 
 #[derive(Deserialize, Debug, Default)]
-#[allow(dead_code)] // may be used later
 pub struct Workspace {
     pub members: Option<Vec<String>>,
     pub exclude: Option<Vec<String>>,
+    /// The members which `cargo build` selects when no package is given.
+    #[serde(rename = "default-members")]
     pub default_members: Option<Vec<String>>,
+    /// The dependencies which members can inherit via `workspace = true`.
+    pub dependencies: Option<Dependencies>,
 }
@@ -0,0 +1,138 @@
+use crate::graph::Graph;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashSet};
+
+/// An external dependency which is declared differently by the members of the workspace.
+#[derive(Debug, PartialEq, Eq)]
+pub struct VersionDrift {
+    pub name: String,
+    pub declarations: Vec<Declaration>,
+}
+
+/// How a single member declares an external dependency.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct Declaration {
+    pub member: String,
+    pub version: Option<String>,
+    pub features: Vec<String>,
+    pub default_features: bool,
+    pub inherited: bool,
+}
+
+impl VersionDrift {
+    /// Whether some members declare the dependency themselves, instead of inheriting it from the workspace.
+    pub fn is_hoistable(&self) -> bool {
+        self.declarations
+            .iter()
+            .any(|declaration| !declaration.inherited)
+    }
+}
+
+impl Graph {
+    /// Finds the external dependencies which are declared by more than one member
+    /// with differing version requirements, features or default features.
+    /// Must be called on the graph before the external dependencies are filtered out.
+    pub fn find_version_drift(&self) -> Vec<VersionDrift> {
+        let mut declarations: BTreeMap<&str, Vec<Declaration>> = BTreeMap::new();
+
+        for (member, dependencies) in &self.adjacency_list {
+            for dep in dependencies {
+                if self.adjacency_list.contains_key(dep) {
+                    continue;
+                }
+                for info in self.get_edge_infos(member, dep) {
+                    let mut features = info.features.clone();
+                    features.sort();
+                    features.dedup();
+                    declarations.entry(dep).or_default().push(Declaration {
+                        member: member.clone(),
+                        version: info.version.clone(),
+                        features,
+                        default_features: !info.no_default_features,
+                        inherited: info.inherited,
+                    });
+                }
+            }
+        }
+
+        declarations
+            .into_iter()
+            .filter_map(|(name, mut declarations)| {
+                declarations.sort();
+                declarations.dedup();
+
+                let members: HashSet<&str> =
+                    declarations.iter().map(|d| d.member.as_str()).collect();
+                let variants: HashSet<_> = declarations
+                    .iter()
+                    .map(|d| (&d.version, &d.features, d.default_features, d.inherited))
+                    .collect();
+
+                (members.len() > 1 && variants.len() > 1).then(|| VersionDrift {
+                    name: name.to_string(),
+                    declarations,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::EdgeInfo;
+
+    fn declare(graph: &mut Graph, member: &str, dep: &str, version: &str, features: &[&str]) {
+        graph.add_edge_with_info(
+            member,
+            dep,
+            EdgeInfo {
+                version: Some(version.to_string()),
+                features: features.iter().map(|f| f.to_string()).collect(),
+                ..Default::default()
+            },
+        );
+    }
+
+    #[test]
+    fn test_version_drift() {
+        let mut graph = Graph::new();
+        declare(&mut graph, "app", "serde", "1.0.150", &["derive"]);
+        declare(&mut graph, "core", "serde", "1.0.200", &["derive"]);
+        declare(&mut graph, "app", "log", "0.4", &[]);
+        declare(&mut graph, "core", "log", "0.4", &[]);
+        declare(&mut graph, "app", "tokio", "1", &["rt"]);
+        declare(&mut graph, "core", "tokio", "1", &["macros", "rt"]);
+        declare(&mut graph, "app", "regex", "1", &[]);
+        // dependencies on workspace members are not external
+        declare(&mut graph, "app", "core", "0.1", &[]);
+
+        let drift = graph.find_version_drift();
+        let names: Vec<&str> = drift.iter().map(|d| d.name.as_str()).collect();
+
+        // `log` is declared identically and `regex` only once
+        assert_eq!(names, vec!["serde", "tokio"]);
+        assert_eq!(drift[0].declarations.len(), 2);
+        assert_eq!(drift[0].declarations[0].version.as_deref(), Some("1.0.150"));
+        assert!(drift[0].is_hoistable());
+    }
+
+    #[test]
+    fn test_default_features_drift() {
+        let mut graph = Graph::new();
+        declare(&mut graph, "app", "serde", "1", &[]);
+        graph.add_edge_with_info(
+            "core",
+            "serde",
+            EdgeInfo {
+                version: Some("1".to_string()),
+                no_default_features: true,
+                ..Default::default()
+            },
+        );
+
+        let drift = graph.find_version_drift();
+        assert_eq!(drift.len(), 1);
+        assert!(!drift[0].declarations[1].default_features);
+    }
+}
@@ -71,5 +71,664 @@ fn test_invalid_argument() {
         .unwrap()
         .arg("--invalid-option")
         .assert()
+        .failure()
+        .code(2);
+}
+
+#[test]
+fn test_invalid_workspace() {
+    let temp_dir = create_tmp_workspace();
+    temp_dir
+        .child("Cargo.toml")
+        .write_str("[workspace]\nmembers = [package1]\n")
+        .unwrap();
+
+    let output = Command::cargo_bin("cargo-workspace-analyzer")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(3));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("Cargo.toml:2:12"));
+}
+
+#[test]
+fn test_skipped_manifests() {
+    let temp_dir = create_tmp_workspace();
+    temp_dir
+        .child("Cargo.toml")
+        .write_str("[workspace]\nmembers = [\"package1\", \"template\", \"missing\"]\n")
+        .unwrap();
+    temp_dir
+        .child("template/Cargo.toml")
+        .write_str("[package]\nname = {{name}}\n")
+        .unwrap();
+
+    let output = Command::cargo_bin("cargo-workspace-analyzer")
+        .unwrap()
+        .arg("--format")
+        .arg("json")
+        .current_dir(&temp_dir)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["nodes"][0]["name"], "package1");
+    assert!(report["skipped_manifests"][0]["reason"]
+        .as_str()
+        .unwrap()
+        .starts_with("line 2, column 9"));
+    assert_eq!(
+        report["skipped_manifests"][1]["reason"],
+        "declared in workspace.members, but doesn't exist"
+    );
+
+    // with --strict, skipped manifests are a policy violation
+    let output = Command::cargo_bin("cargo-workspace-analyzer")
+        .unwrap()
+        .arg("--strict")
+        .current_dir(&temp_dir)
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(4));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("2 manifest(s) were skipped:"));
+}
+
+#[test]
+fn test_metadata_from_stdin() {
+    let temp_dir = assert_fs::TempDir::new().unwrap();
+    let metadata = r#"{
+        "packages": [
+            {"name": "app", "id": "app 0.1.0", "dependencies": [
+                {"name": "core", "kind": null, "rename": null, "optional": false,
+                 "features": [], "uses_default_features": true, "target": null}
+            ]},
+            {"name": "core", "id": "core 0.1.0", "dependencies": []}
+        ],
+        "workspace_members": ["app 0.1.0", "core 0.1.0"],
+        "resolve": null
+    }"#;
+
+    // the directory is not searched for manifests, so the glob to exclude directories is not used
+    Command::cargo_bin("cargo-workspace-analyzer")
+        .unwrap()
+        .arg("--metadata")
+        .arg("-")
+        .arg("--exclude-dir")
+        .arg("[")
+        .arg("-o")
+        .arg("mmd")
+        .write_stdin(metadata)
+        .current_dir(&temp_dir)
+        .assert()
+        .success();
+
+    let output_file = temp_dir.child("workspace-analyzer.mmd");
+    let content = std::fs::read_to_string(output_file.path()).unwrap();
+    assert!(content.contains("app --> core"));
+}
+
+#[test]
+fn test_json_report() {
+    let temp_dir = create_tmp_workspace();
+
+    let output = Command::cargo_bin("cargo-workspace-analyzer")
+        .unwrap()
+        .arg("--format")
+        .arg("json")
+        .arg("-o")
+        .arg("mmd")
+        .current_dir(&temp_dir)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["schema_version"], 1);
+    assert_eq!(report["nodes"][0]["name"], "package1");
+}
+
+#[test]
+fn test_dot_export() {
+    let temp_dir = create_tmp_workspace();
+
+    Command::cargo_bin("cargo-workspace-analyzer")
+        .unwrap()
+        .arg("-o")
+        .arg("dot")
+        .current_dir(&temp_dir)
+        .assert()
+        .success();
+
+    let output_file = temp_dir.child("workspace-analyzer.dot");
+    let content = std::fs::read_to_string(output_file.path()).unwrap();
+    assert!(content.starts_with("digraph workspace {"));
+    assert!(content.contains("\"package1\";"));
+}
+
+#[test]
+fn test_html_export() {
+    let temp_dir = create_tmp_workspace();
+
+    Command::cargo_bin("cargo-workspace-analyzer")
+        .unwrap()
+        .arg("-o")
+        .arg("html")
+        .current_dir(&temp_dir)
+        .assert()
+        .success();
+
+    let output_file = temp_dir.child("workspace-analyzer.html");
+    let content = std::fs::read_to_string(output_file.path()).unwrap();
+    assert!(content.starts_with("<!DOCTYPE html>"));
+    assert!(content.contains("data-name=\"package1\""));
+}
+
+#[test]
+fn test_output_path() {
+    let temp_dir = create_tmp_workspace();
+
+    Command::cargo_bin("cargo-workspace-analyzer")
+        .unwrap()
+        .arg("-o")
+        .arg("mmd")
+        .arg("--output")
+        .arg("diagrams/graph.mmd")
+        .current_dir(&temp_dir)
+        .assert()
         .failure();
+
+    temp_dir.child("diagrams").create_dir_all().unwrap();
+    Command::cargo_bin("cargo-workspace-analyzer")
+        .unwrap()
+        .arg("-o")
+        .arg("mmd")
+        .arg("--output")
+        .arg("diagrams/graph.mmd")
+        .current_dir(&temp_dir)
+        .assert()
+        .success();
+
+    assert!(temp_dir.child("diagrams/graph.mmd").exists());
+    assert!(!temp_dir.child("workspace-analyzer.mmd").exists());
+}
+
+#[test]
+fn test_output_to_stdout() {
+    let temp_dir = create_tmp_workspace();
+
+    let output = Command::cargo_bin("cargo-workspace-analyzer")
+        .unwrap()
+        .arg("-o")
+        .arg("dot")
+        .arg("--output")
+        .arg("-")
+        .current_dir(&temp_dir)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    // only the diagram is written to stdout, so it can be piped into `dot`
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.starts_with("digraph workspace {\n"));
+    assert!(stdout.ends_with("}\n"));
+    assert_eq!(stdout.matches("digraph").count(), 1);
+    assert!(!stdout.contains("Category"));
+    assert!(!temp_dir.child("workspace-analyzer.dot").exists());
+
+    let output = Command::cargo_bin("cargo-workspace-analyzer")
+        .unwrap()
+        .arg("--format")
+        .arg("json")
+        .arg("-o")
+        .arg("mmd")
+        .arg("--output")
+        .arg("-")
+        .current_dir(&temp_dir)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.starts_with("graph TD\n"));
+    assert!(!stdout.contains("schema_version"));
+}
+
+#[test]
+fn test_single_package() {
+    let temp_dir = assert_fs::TempDir::new().unwrap();
+    temp_dir
+        .child("Cargo.toml")
+        .write_str(
+            r#"
+[package]
+name = "app"
+version = "0.1.0"
+
+[dependencies]
+util = { path = "util" }
+    "#,
+        )
+        .unwrap();
+    temp_dir.child("src").create_dir_all().unwrap();
+    temp_dir
+        .child("util/Cargo.toml")
+        .write_str(
+            r#"
+[package]
+name = "util"
+version = "0.1.0"
+    "#,
+        )
+        .unwrap();
+
+    let output = Command::cargo_bin("cargo-workspace-analyzer")
+        .unwrap()
+        .arg("-o")
+        .arg("dot")
+        .arg("--output")
+        .arg("-")
+        .current_dir(&temp_dir)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("\"app\" -> \"util\""));
+}
+
+#[test]
+fn test_default_members_scope() {
+    let temp_dir = assert_fs::TempDir::new().unwrap();
+    temp_dir
+        .child("Cargo.toml")
+        .write_str(
+            r#"
+[workspace]
+members = ["app", "core", "bench"]
+default-members = ["app"]
+    "#,
+        )
+        .unwrap();
+    temp_dir
+        .child("app/Cargo.toml")
+        .write_str("[package]\nname = \"app\"\n[dependencies]\ncore = { path = \"../core\" }\n")
+        .unwrap();
+    temp_dir
+        .child("core/Cargo.toml")
+        .write_str("[package]\nname = \"core\"\n")
+        .unwrap();
+    temp_dir
+        .child("bench/Cargo.toml")
+        .write_str("[package]\nname = \"bench\"\n[dependencies]\napp = { path = \"../app\" }\n")
+        .unwrap();
+
+    let dot = |scope: &str| {
+        let output = Command::cargo_bin("cargo-workspace-analyzer")
+            .unwrap()
+            .arg("--scope")
+            .arg(scope)
+            .arg("-o")
+            .arg("dot")
+            .arg("--output")
+            .arg("-")
+            .current_dir(&temp_dir)
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        String::from_utf8(output.stdout).unwrap()
+    };
+
+    let all = dot("all");
+    assert!(all.contains("\"bench\" -> \"app\""));
+
+    let default_members = dot("default-members");
+    assert!(default_members.contains("\"app\""));
+    assert!(!default_members.contains("\"core\""));
+    assert!(!default_members.contains("\"bench\""));
+
+    let with_dependencies = dot("default-members+deps");
+    assert!(with_dependencies.contains("\"app\" -> \"core\""));
+    assert!(!with_dependencies.contains("\"bench\""));
+
+    // members outside the scope are still members in the lockfile, and not reported as external crates
+    temp_dir
+        .child("Cargo.lock")
+        .write_str(
+            r#"
+version = 3
+
+[[package]]
+name = "app"
+version = "0.1.0"
+dependencies = ["core", "serde"]
+
+[[package]]
+name = "core"
+version = "0.1.0"
+dependencies = ["log"]
+
+[[package]]
+name = "log"
+version = "0.4.21"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "serde"
+version = "1.0.200"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+    "#,
+        )
+        .unwrap();
+    let output = Command::cargo_bin("cargo-workspace-analyzer")
+        .unwrap()
+        .arg("--scope")
+        .arg("default-members")
+        .arg("--external")
+        .arg("--format")
+        .arg("json")
+        .arg("-o")
+        .arg("dot")
+        .arg("--output")
+        .arg("graph.dot")
+        .current_dir(&temp_dir)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(
+        report["external"]["members"],
+        serde_json::json!([{"package": "app", "direct": 1, "transitive": 1, "depth": 1}])
+    );
+    assert_eq!(report["external"]["crates"][0]["name"], "serde");
+    assert_eq!(report["external"]["crates"].as_array().unwrap().len(), 1);
+}
+
+#[test]
+fn test_external_path_dependencies() {
+    let temp_dir = assert_fs::TempDir::new().unwrap();
+    let workspace = temp_dir.child("workspace");
+    workspace
+        .child("Cargo.toml")
+        .write_str("[workspace]\nmembers = [\"app\"]\n")
+        .unwrap();
+    workspace
+        .child("app/Cargo.toml")
+        .write_str(
+            "[package]\nname = \"app\"\n[dependencies]\nfoo = { path = \"../../shared/foo\" }\n",
+        )
+        .unwrap();
+    temp_dir
+        .child("shared/foo/Cargo.toml")
+        .write_str("[package]\nname = \"foo\"\n")
+        .unwrap();
+
+    let output = Command::cargo_bin("cargo-workspace-analyzer")
+        .unwrap()
+        .arg("--format")
+        .arg("json")
+        .arg("-o")
+        .arg("dot")
+        .current_dir(&workspace)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["nodes"][1]["name"], "foo");
+    assert_eq!(report["nodes"][1]["category"], "external_path");
+    assert_eq!(report["counts"]["workspace"]["packages"], 1);
+    assert_eq!(report["counts"]["external_path"]["packages"], 1);
+    assert_eq!(report["edges"][0]["to"], "foo");
+}
+
+#[test]
+fn test_path_dependencies_within_the_workspace_are_members() {
+    let temp_dir = assert_fs::TempDir::new().unwrap();
+    temp_dir
+        .child("Cargo.toml")
+        .write_str("[workspace]\nmembers = [\"app\"]\n")
+        .unwrap();
+    temp_dir
+        .child("app/Cargo.toml")
+        .write_str("[package]\nname = \"app\"\n[dependencies]\ngen = { path = \"../tools/gen\" }\n")
+        .unwrap();
+    temp_dir
+        .child("tools/gen/Cargo.toml")
+        .write_str("[package]\nname = \"gen\"\n")
+        .unwrap();
+
+    let output = Command::cargo_bin("cargo-workspace-analyzer")
+        .unwrap()
+        .arg("--format")
+        .arg("json")
+        .arg("-o")
+        .arg("dot")
+        .current_dir(&temp_dir)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["nodes"][1]["name"], "gen");
+    assert_eq!(report["nodes"][1]["category"], "workspace");
+    assert_eq!(report["counts"]["workspace"]["packages"], 2);
+    assert_eq!(report["counts"]["external_path"]["packages"], 0);
+}
+
+#[test]
+fn test_external_path_inherits_from_its_own_workspace() {
+    let temp_dir = assert_fs::TempDir::new().unwrap();
+    let workspace = temp_dir.child("workspace");
+    workspace
+        .child("Cargo.toml")
+        .write_str("[workspace]\nmembers = [\"app\", \"util\"]\n[workspace.dependencies]\nutil = { path = \"util\" }\n")
+        .unwrap();
+    workspace
+        .child("app/Cargo.toml")
+        .write_str(
+            "[package]\nname = \"app\"\n[dependencies]\nfoo = { path = \"../../shared/foo\" }\n",
+        )
+        .unwrap();
+    workspace
+        .child("util/Cargo.toml")
+        .write_str("[package]\nname = \"util\"\n")
+        .unwrap();
+    temp_dir
+        .child("shared/Cargo.toml")
+        .write_str("[workspace]\nmembers = [\"foo\", \"util\"]\n[workspace.dependencies]\nutil = { path = \"util\", package = \"shared-util\" }\n")
+        .unwrap();
+    temp_dir
+        .child("shared/foo/Cargo.toml")
+        .write_str("[package]\nname = \"foo\"\n[dependencies]\nutil = { workspace = true }\n")
+        .unwrap();
+    temp_dir
+        .child("shared/util/Cargo.toml")
+        .write_str("[package]\nname = \"shared-util\"\n")
+        .unwrap();
+
+    let output = Command::cargo_bin("cargo-workspace-analyzer")
+        .unwrap()
+        .arg("--format")
+        .arg("json")
+        .arg("-o")
+        .arg("dot")
+        .current_dir(&workspace)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    // `foo` uses the `util` of its own workspace, not the one of the analyzed workspace
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let foo_edges: Vec<&serde_json::Value> = report["edges"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|edge| edge["from"] == "foo")
+        .collect();
+    assert_eq!(foo_edges.len(), 1);
+    assert_eq!(foo_edges[0]["to"], "shared-util");
+    assert_eq!(foo_edges[0]["aliases"][0], "util");
+    assert_eq!(report["counts"]["external_path"]["packages"], 2);
+}
+
+#[test]
+fn test_cycle_report() {
+    let temp_dir = assert_fs::TempDir::new().unwrap();
+    temp_dir
+        .child("Cargo.toml")
+        .write_str("[workspace]\nmembers = [\"a\", \"b\", \"c\"]\n")
+        .unwrap();
+    for (name, dependencies) in [("a", "b"), ("b", "a"), ("c", "a")] {
+        temp_dir
+            .child(format!("{}/Cargo.toml", name))
+            .write_str(&format!(
+                "[package]\nname = \"{}\"\n[dependencies]\n{} = {{ path = \"../{}\" }}\n",
+                name, dependencies, dependencies
+            ))
+            .unwrap();
+    }
+
+    let output = Command::cargo_bin("cargo-workspace-analyzer")
+        .unwrap()
+        .arg("-o")
+        .arg("mmd")
+        .current_dir(&temp_dir)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("│ 1         │ a, b     │"));
+    assert!(stdout.contains("a → b → a"));
+    assert!(stdout.contains("│ a → b             │ 1         │ 1      │"));
+    assert!(stdout.contains("Removing the 1 dependency listed above makes the workspace acyclic."));
+
+    let output = Command::cargo_bin("cargo-workspace-analyzer")
+        .unwrap()
+        .arg("--format")
+        .arg("json")
+        .arg("--cycle-limit")
+        .arg("0")
+        .arg("-o")
+        .arg("mmd")
+        .current_dir(&temp_dir)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(
+        report["cycles"]["components"],
+        serde_json::json!([["a", "b"]])
+    );
+    assert_eq!(report["cycles"]["paths"], serde_json::json!([]));
+    assert_eq!(report["cycles"]["truncated"], true);
+}
+
+#[test]
+fn test_condensed_diagram() {
+    let temp_dir = assert_fs::TempDir::new().unwrap();
+    temp_dir
+        .child("Cargo.toml")
+        .write_str("[workspace]\nmembers = [\"a\", \"b\", \"app\"]\n")
+        .unwrap();
+    for (name, dependency) in [("a", "b"), ("b", "a"), ("app", "a")] {
+        temp_dir
+            .child(format!("{}/Cargo.toml", name))
+            .write_str(&format!(
+                "[package]\nname = \"{}\"\n[dependencies]\n{} = {{ path = \"../{}\" }}\n",
+                name, dependency, dependency
+            ))
+            .unwrap();
+    }
+
+    let output = Command::cargo_bin("cargo-workspace-analyzer")
+        .unwrap()
+        .arg("--condense")
+        .arg("--format")
+        .arg("json")
+        .arg("-o")
+        .arg("mmd")
+        .arg("--output")
+        .arg("graph.mmd")
+        .current_dir(&temp_dir)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["nodes"][0]["name"], "Component 1");
+    assert_eq!(report["nodes"][0]["category"], "component");
+    assert_eq!(report["nodes"][0]["members"], serde_json::json!(["a", "b"]));
+    // the cycles are still reported for the packages
+    assert_eq!(
+        report["cycles"]["components"],
+        serde_json::json!([["a", "b"]])
+    );
+
+    let diagram = std::fs::read_to_string(temp_dir.child("graph.mmd").path()).unwrap();
+    assert!(diagram.contains("app --> Component_1"));
+    assert!(!diagram.contains(":::red"));
+}
+
+#[test]
+fn test_layers() {
+    let temp_dir = assert_fs::TempDir::new().unwrap();
+    temp_dir
+        .child("Cargo.toml")
+        .write_str("[workspace]\nmembers = [\"app\", \"service\", \"core\"]\n")
+        .unwrap();
+    for (name, dependency) in [("app", "service"), ("service", "core")] {
+        temp_dir
+            .child(format!("{}/Cargo.toml", name))
+            .write_str(&format!(
+                "[package]\nname = \"{}\"\n[dependencies]\n{} = {{ path = \"../{}\" }}\n",
+                name, dependency, dependency
+            ))
+            .unwrap();
+    }
+    temp_dir
+        .child("core/Cargo.toml")
+        .write_str("[package]\nname = \"core\"\n")
+        .unwrap();
+
+    let output = Command::cargo_bin("cargo-workspace-analyzer")
+        .unwrap()
+        .arg("--layers")
+        .arg("-o")
+        .arg("mmd")
+        .arg("--output")
+        .arg("graph.mmd")
+        .current_dir(&temp_dir)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("│ 2     │ app      │"));
+    assert!(stdout.contains("│ 0     │ core     │"));
+
+    let diagram = std::fs::read_to_string(temp_dir.child("graph.mmd").path()).unwrap();
+    assert!(diagram.contains("    subgraph level_1[\"Level 1\"]\n        service\n    end\n"));
+
+    let output = Command::cargo_bin("cargo-workspace-analyzer")
+        .unwrap()
+        .arg("--format")
+        .arg("json")
+        .arg("-o")
+        .arg("mmd")
+        .arg("--output")
+        .arg("graph.mmd")
+        .current_dir(&temp_dir)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(
+        report["layers"],
+        serde_json::json!([["core"], ["service"], ["app"]])
+    );
+    assert_eq!(report["coupling"][0]["package"], "app");
+    assert_eq!(report["coupling"][0]["level"], 2);
 }